@@ -0,0 +1,114 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tokio::task::spawn_blocking;
+
+use crate::{
+    board::Board,
+    error::CgosResult,
+    fan::Fan,
+    status::Status,
+    temperature::Temperature,
+    units::{Celsius, Rpm, Volt},
+    voltage::Voltage,
+};
+
+/// Runs the blocking libcgos calls on tokio's blocking pool. Calls are serialized through a
+/// mutex because libcgos does not document concurrent use of one handle. The board has to live
+/// for the rest of the program, e.g. by obtaining it from a leaked `Congatec`.
+#[derive(Clone)]
+pub struct AsyncBoard {
+    board: Arc<Mutex<Board<'static>>>,
+}
+
+impl AsyncBoard {
+    pub fn new(board: Board<'static>) -> Self {
+        Self {
+            board: Arc::new(Mutex::new(board)),
+        }
+    }
+
+    pub fn temperature(&self, index: usize) -> AsyncTemperature {
+        AsyncTemperature {
+            board: self.clone(),
+            index,
+        }
+    }
+
+    pub fn fan(&self, index: usize) -> AsyncFan {
+        AsyncFan {
+            board: self.clone(),
+            index,
+        }
+    }
+
+    pub fn voltage(&self, index: usize) -> AsyncVoltage {
+        AsyncVoltage {
+            board: self.clone(),
+            index,
+        }
+    }
+
+    async fn run<T: Send + 'static>(
+        &self,
+        operation: impl FnOnce(&Board<'static>) -> CgosResult<T> + Send + 'static,
+    ) -> CgosResult<T> {
+        let board = self.board.clone();
+        spawn_blocking(move || {
+            let board = board.lock().unwrap_or_else(PoisonError::into_inner);
+            operation(&board)
+        })
+        .await
+        .expect("blocking CGOS call panicked")
+    }
+}
+
+pub struct AsyncTemperature {
+    board: AsyncBoard,
+    index: usize,
+}
+
+impl AsyncTemperature {
+    pub async fn current(&self) -> CgosResult<(Celsius, Status)> {
+        let index = self.index;
+        self.board
+            .run(move |board| {
+                let amount = board.get_number_of_temperatures()?;
+                Temperature::new(board, index, amount)?.current()
+            })
+            .await
+    }
+}
+
+pub struct AsyncFan {
+    board: AsyncBoard,
+    index: usize,
+}
+
+impl AsyncFan {
+    pub async fn current(&self) -> CgosResult<(Rpm, Status)> {
+        let index = self.index;
+        self.board
+            .run(move |board| {
+                let amount = board.get_number_of_fans()?;
+                Fan::new(board, index, amount)?.current()
+            })
+            .await
+    }
+}
+
+pub struct AsyncVoltage {
+    board: AsyncBoard,
+    index: usize,
+}
+
+impl AsyncVoltage {
+    pub async fn current(&self) -> CgosResult<(Volt, Status)> {
+        let index = self.index;
+        self.board
+            .run(move |board| {
+                let amount = board.get_number_of_voltages()?;
+                Voltage::new(board, index, amount)?.current()
+            })
+            .await
+    }
+}
@@ -5,4 +5,28 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
+#[cfg(not(test))]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+// Unit tests swap the libcgos functions they exercise for the stand-ins in `mock`. Explicitly
+// re-exported items take precedence over the glob import of the generated bindings.
+#[cfg(test)]
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+
+#[cfg(test)]
+pub use generated::*;
+
+#[cfg(test)]
+pub(crate) mod mock;
+
+#[cfg(test)]
+pub use mock::{
+    CgosBoardClose, CgosBoardCount, CgosBoardGetBootCounter, CgosBoardGetInfoA,
+    CgosBoardGetRunningTimeMeter, CgosBoardOpen, CgosBoardOpenByNameA, CgosFanGetCurrent,
+    CgosFanGetInfo, CgosFanSetLimits, CgosLibGetLastError, CgosLibInitialize, CgosLibUninitialize,
+    CgosTemperatureCount, CgosTemperatureGetCurrent, CgosTemperatureGetInfo,
+    CgosTemperatureSetLimits, CgosVoltageGetCurrent, CgosVoltageGetInfo, CgosVoltageSetLimits,
+    CgosWDogDisable, CgosWDogSetConfigStruct, CgosWDogTrigger,
+};
@@ -0,0 +1,223 @@
+//! Stand-ins for the libcgos functions covered by unit tests. Every call is recorded, succeeds
+//! unless the function was told to fail, and writes the value configured for the function to its
+//! output parameter. The state is per thread, so tests running in parallel do not interfere.
+
+use std::{cell::RefCell, collections::HashMap, os::raw::c_char};
+
+use super::{CGOSBOARDINFOA, CGOSFANINFO, CGOSTEMPERATUREINFO, CGOSVOLTAGEINFO, CGOSWDCONFIG};
+use crate::{
+    board::{Board, BoardClass},
+    error::{CgosError, CgosResult},
+};
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::default();
+}
+
+#[derive(Default)]
+struct State {
+    calls: Vec<&'static str>,
+    failures: HashMap<&'static str, u32>,
+    values: HashMap<&'static str, u32>,
+    serial_number: String,
+    last_error: u32,
+}
+
+/// Makes `function` return zero and report `error` as the last error from now on.
+pub(crate) fn fail(function: &'static str, error: u32) {
+    STATE.with(|state| state.borrow_mut().failures.insert(function, error));
+}
+
+pub(crate) fn set_value(function: &'static str, value: u32) {
+    STATE.with(|state| state.borrow_mut().values.insert(function, value));
+}
+
+/// Sets the serial number reported by `CgosBoardGetInfoA`, e.g. to simulate a swapped board.
+pub(crate) fn set_serial_number(serial_number: &str) {
+    STATE.with(|state| state.borrow_mut().serial_number = serial_number.to_owned());
+}
+
+pub(crate) fn calls() -> Vec<&'static str> {
+    STATE.with(|state| state.borrow().calls.clone())
+}
+
+/// Opens the single CPU board the mocked library reports.
+pub(crate) fn open_board() -> Board<'static> {
+    set_value("CgosBoardCount", 1);
+    Board::try_new(BoardClass::CPU, 0).expect("failed to open mocked board")
+}
+
+/// Asserts that `result` is the failure of `function` with the last error `error`.
+pub(crate) fn assert_failed<T>(result: CgosResult<T>, function: &str, error: u32) {
+    match result {
+        Err(CgosError::CallFailed {
+            function: failed,
+            last_error,
+        }) => {
+            assert_eq!(failed, function);
+            assert_eq!(last_error, error);
+        }
+        Err(other) => panic!("expected {function} to fail, got {other}"),
+        Ok(_) => panic!("expected {function} to fail, but it succeeded"),
+    }
+}
+
+fn call(function: &'static str) -> (u32, u32) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.calls.push(function);
+        let value = state.values.get(function).copied().unwrap_or_default();
+        match state.failures.get(function).copied() {
+            Some(error) => {
+                state.last_error = error;
+                (0, value)
+            }
+            None => (1, value),
+        }
+    })
+}
+
+pub unsafe fn CgosLibInitialize() -> u32 {
+    call("CgosLibInitialize").0
+}
+
+pub unsafe fn CgosLibUninitialize() -> u32 {
+    call("CgosLibUninitialize").0
+}
+
+pub unsafe fn CgosLibGetLastError() -> u32 {
+    STATE.with(|state| state.borrow().last_error)
+}
+
+pub unsafe fn CgosBoardCount(_class: u32, _flags: u32) -> u32 {
+    match call("CgosBoardCount") {
+        (0, _) => 0,
+        (_, amount) => amount,
+    }
+}
+
+pub unsafe fn CgosBoardOpen(_class: u32, index: u32, _flags: u32, handle: *mut u32) -> u32 {
+    *handle = index + 1;
+    call("CgosBoardOpen").0
+}
+
+pub unsafe fn CgosBoardOpenByNameA(_name: *const c_char, handle: *mut u32) -> u32 {
+    *handle = 1;
+    call("CgosBoardOpenByNameA").0
+}
+
+pub unsafe fn CgosBoardClose(_handle: u32) -> u32 {
+    call("CgosBoardClose").0
+}
+
+pub unsafe fn CgosBoardGetInfoA(_handle: u32, info: *mut CGOSBOARDINFOA) -> u32 {
+    let (result, classes) = call("CgosBoardGetInfoA");
+    (*info).dwClasses = classes;
+    (*info).dwPrimaryClass = classes;
+    STATE.with(|state| {
+        let serial_number = &state.borrow().serial_number;
+        for (target, byte) in (*info).szSerialNumber.iter_mut().zip(serial_number.bytes()) {
+            *target = byte as c_char;
+        }
+    });
+    result
+}
+
+pub unsafe fn CgosBoardGetBootCounter(_handle: u32, count: *mut u32) -> u32 {
+    let (result, value) = call("CgosBoardGetBootCounter");
+    *count = value;
+    result
+}
+
+pub unsafe fn CgosBoardGetRunningTimeMeter(_handle: u32, count: *mut u32) -> u32 {
+    let (result, value) = call("CgosBoardGetRunningTimeMeter");
+    *count = value;
+    result
+}
+
+pub unsafe fn CgosTemperatureCount(_handle: u32) -> u32 {
+    match call("CgosTemperatureCount") {
+        (0, _) => 0,
+        (_, amount) => amount,
+    }
+}
+
+pub unsafe fn CgosTemperatureGetInfo(
+    _handle: u32,
+    _unit: u32,
+    _info: *mut CGOSTEMPERATUREINFO,
+) -> u32 {
+    call("CgosTemperatureGetInfo").0
+}
+
+pub unsafe fn CgosTemperatureGetCurrent(
+    _handle: u32,
+    _unit: u32,
+    setting: *mut u32,
+    status: *mut u32,
+) -> u32 {
+    let (result, value) = call("CgosTemperatureGetCurrent");
+    *setting = value;
+    *status = 0;
+    result
+}
+
+pub unsafe fn CgosTemperatureSetLimits(
+    _handle: u32,
+    _unit: u32,
+    _info: *mut CGOSTEMPERATUREINFO,
+) -> u32 {
+    call("CgosTemperatureSetLimits").0
+}
+
+pub unsafe fn CgosFanGetInfo(_handle: u32, _unit: u32, _info: *mut CGOSFANINFO) -> u32 {
+    call("CgosFanGetInfo").0
+}
+
+pub unsafe fn CgosFanGetCurrent(
+    _handle: u32,
+    _unit: u32,
+    setting: *mut u32,
+    status: *mut u32,
+) -> u32 {
+    let (result, value) = call("CgosFanGetCurrent");
+    *setting = value;
+    *status = 0;
+    result
+}
+
+pub unsafe fn CgosFanSetLimits(_handle: u32, _unit: u32, _info: *mut CGOSFANINFO) -> u32 {
+    call("CgosFanSetLimits").0
+}
+
+pub unsafe fn CgosVoltageGetInfo(_handle: u32, _unit: u32, _info: *mut CGOSVOLTAGEINFO) -> u32 {
+    call("CgosVoltageGetInfo").0
+}
+
+pub unsafe fn CgosVoltageGetCurrent(
+    _handle: u32,
+    _unit: u32,
+    setting: *mut u32,
+    status: *mut u32,
+) -> u32 {
+    let (result, value) = call("CgosVoltageGetCurrent");
+    *setting = value;
+    *status = 0;
+    result
+}
+
+pub unsafe fn CgosVoltageSetLimits(_handle: u32, _unit: u32, _info: *mut CGOSVOLTAGEINFO) -> u32 {
+    call("CgosVoltageSetLimits").0
+}
+
+pub unsafe fn CgosWDogSetConfigStruct(_handle: u32, _unit: u32, _config: *mut CGOSWDCONFIG) -> u32 {
+    call("CgosWDogSetConfigStruct").0
+}
+
+pub unsafe fn CgosWDogTrigger(_handle: u32, _unit: u32) -> u32 {
+    call("CgosWDogTrigger").0
+}
+
+pub unsafe fn CgosWDogDisable(_handle: u32, _unit: u32) -> u32 {
+    call("CgosWDogDisable").0
+}
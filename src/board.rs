@@ -1,145 +1,636 @@
 use std::{
+    cell::Cell,
     ffi::{CStr, CString},
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
     marker::PhantomData,
-    mem::{size_of, zeroed},
+    mem::{size_of, zeroed, ManuallyDrop},
+    ptr,
+    sync::OnceLock,
     time::Duration,
 };
 
 use bitflags::bitflags;
+#[cfg(feature = "chrono")]
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+#[cfg(feature = "time")]
+use time::{Date, Month, PrimitiveDateTime, Time};
 
 use crate::{
     bindings::{
-        CgosBoardClose, CgosBoardCount, CgosBoardGetBootCounter, CgosBoardGetInfoA,
-        CgosBoardGetNameA, CgosBoardOpen, CgosBoardOpenByNameA, CGOSBOARDINFOA, CGOSTIME,
+        CgosBoardClose, CgosBoardCount, CgosBoardGetBootCounter, CgosBoardGetBootErrorLog,
+        CgosBoardGetInfoA, CgosBoardGetNameA, CgosBoardGetOption, CgosBoardGetRunningTimeMeter,
+        CgosBoardOpen, CgosBoardOpenByNameA, CgosBoardSetOption, CGOSBOARDINFOA, CGOSTIME,
         CGOS_BOARD_CLASS_CPU, CGOS_BOARD_CLASS_IO, CGOS_BOARD_CLASS_VGA,
     },
-    fan::Fan,
+    error::{check, check_amount, CgosError, CgosResult},
+    fan::{Fan, FanConfig, FanConfigReport, FanInfo, FanOutcome, FanSelector, SelectorReport},
+    io::Io,
+    status::Status,
     storage_area::{StorageArea, StorageAreaType},
-    temperature::Temperature,
+    temperature::{Temperature, TemperatureInfo},
+    units::{Celsius, Rpm, Volt},
+    vga::Vga,
+    voltage::{Voltage, VoltageInfo},
+    watchdog::Watchdog,
 };
 
 pub const FLAGS: u32 = 0;
 
+/// Boards hold the numeric CGOS handle, which is valid process-wide, together with how they were
+/// opened, their identity and cached subsystem counts. They are `Send`, so they can be moved to a
+/// polling thread, but not `Sync`: libcgos does not document concurrent calls on one handle, so
+/// a board is shared between threads through `sync::SyncBoard`, `AsyncBoard` or `Monitor`, which
+/// serialize the calls.
+///
+/// Subsystems such as sensors and storage areas borrow their board and call through its handle,
+/// so their failures are reported as [`CgosError::BoardRemoved`] once the board is gone, like
+/// those of the board itself.
 pub struct Board<'library> {
     handle: u32,
+    origin: BoardOrigin,
+    id: BoardId,
+    counts: Counts,
     _library_lifetime: PhantomData<&'library ()>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+/// Subsystem counts are fixed per board, so each is queried only until it is nonzero and then
+/// cached until the board reconnects.
+#[derive(Default)]
+struct Counts {
+    temperatures: OnceLock<usize>,
+    fans: OnceLock<usize>,
+    voltages: OnceLock<usize>,
+    watchdogs: OnceLock<usize>,
+    vga_channels: OnceLock<usize>,
+    io_units: OnceLock<usize>,
+}
+
+fn cached(count: &OnceLock<usize>, query: impl FnOnce() -> CgosResult<usize>) -> CgosResult<usize> {
+    if let Some(amount) = count.get() {
+        return Ok(*amount);
+    }
+    let amount = query()?;
+    // a zero may stem from a driver that was not ready yet, so it is queried again next time
+    if amount == 0 {
+        return Ok(0);
+    }
+    Ok(*count.get_or_init(|| amount))
+}
+
+#[derive(Clone, Debug)]
+enum BoardOrigin {
+    Index { class: BoardClass, index: usize },
+    Name(CString),
 }
 
 impl<'library> Board<'library> {
-    pub(crate) fn amount(class: BoardClass) -> usize {
-        unsafe { CgosBoardCount(class.bits, FLAGS) as usize }
+    pub(crate) fn amount(class: BoardClass) -> CgosResult<usize> {
+        check_amount(
+            || unsafe { CgosBoardCount(class.to_raw(), FLAGS) },
+            "CgosBoardCount",
+        )
     }
 
-    pub(crate) fn new(class: BoardClass, index: usize) -> Board<'library> {
-        let mut handle = Default::default();
-        assert_ne!(
-            unsafe { CgosBoardOpen(class.bits, index.try_into().unwrap(), FLAGS, &mut handle) },
-            0,
-        );
-        Self {
+    pub(crate) fn try_new(class: BoardClass, index: usize) -> CgosResult<Board<'library>> {
+        let amount = Self::amount(class)?;
+        if index >= amount {
+            return Err(CgosError::OutOfRange {
+                what: "board index",
+                value: index,
+                limit: amount,
+            });
+        }
+        Self::open(BoardOrigin::Index { class, index })
+    }
+
+    pub(crate) fn try_from_name(name: &str) -> Option<Board<'library>> {
+        let name = CString::new(name).ok()?;
+        Self::open(BoardOrigin::Name(name)).ok()
+    }
+
+    fn open(origin: BoardOrigin) -> CgosResult<Board<'library>> {
+        let handle = open_handle(&origin)?;
+        let id = match read_info(handle) {
+            Ok(info) => BoardId::from(info),
+            Err(error) => {
+                unsafe { CgosBoardClose(handle) };
+                return Err(error);
+            }
+        };
+        Ok(Self {
             handle,
+            origin,
+            id,
+            counts: Counts::default(),
             _library_lifetime: PhantomData,
-        }
+            _not_sync: PhantomData,
+        })
     }
 
-    pub(crate) fn from_name(name: &str) -> Board<'library> {
-        let name = CString::new(name).unwrap();
-        let mut handle = Default::default();
-        assert_ne!(
-            unsafe { CgosBoardOpenByNameA(name.as_ptr(), &mut handle) },
-            0,
-        );
-        Self {
+    /// Takes ownership of a handle obtained by calling `bindings` functions directly. The board
+    /// remembers its name so that `reconnect` and `is_present` work as for boards opened by name.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid open CGOS board handle that nothing else closes, since the board
+    /// closes it on drop, and the library must stay initialized for `'library`.
+    ///
+    /// On success the board owns the handle. If reading the board info or name fails, the handle
+    /// is left open and stays owned by the caller.
+    pub unsafe fn from_raw_handle(handle: u32) -> CgosResult<Board<'library>> {
+        let info = read_info(handle)?;
+        Ok(Self {
             handle,
+            origin: BoardOrigin::Name(read_raw_name(handle)?),
+            id: info.into(),
+            counts: Counts::default(),
             _library_lifetime: PhantomData,
+            _not_sync: PhantomData,
+        })
+    }
+
+    /// The CGOS handle for calling `bindings` functions that are not wrapped yet. It stays owned
+    /// by the board and must not be closed.
+    pub fn raw_handle(&self) -> u32 {
+        self.handle
+    }
+
+    /// Opens the same board again by name. The returned board has its own independent handle,
+    /// which is closed when it is dropped, separately from this one.
+    pub fn reopen(&self) -> CgosResult<Board<'library>> {
+        let name = read_raw_name(self.handle).map_err(|error| self.classify(error))?;
+        Self::open(BoardOrigin::Name(name)).map_err(|error| self.classify(error))
+    }
+
+    pub fn reconnect(&mut self) -> CgosResult<()> {
+        // open the new handle first so that a failed reopen leaves the current one intact
+        let handle = open_handle(&self.origin).map_err(|error| self.classify(error))?;
+        // the origin may now lead to a different board, which must not be adopted silently
+        let id = read_info(handle).map(BoardId::from);
+        if !matches!(&id, Ok(id) if *id == self.id) {
+            unsafe { CgosBoardClose(handle) };
+            return Err(id.err().unwrap_or(CgosError::BoardChanged));
         }
+        unsafe { CgosBoardClose(self.handle) };
+        self.handle = handle;
+        self.counts = Counts::default();
+        Ok(())
     }
 
-    pub fn name(&self) -> String {
-        const SIZE: usize = 128;
-        let mut name = vec![0; SIZE];
-        assert_ne!(
-            unsafe { CgosBoardGetNameA(self.handle, name.as_mut_ptr() as *mut i8, SIZE as u32,) },
-            0,
-        );
-        unsafe { CStr::from_ptr(name.as_ptr()) }
-            .to_str()
-            .unwrap()
-            .to_string()
+    pub fn close(self) -> CgosResult<()> {
+        let board = ManuallyDrop::new(self);
+        // skip `Drop` so the handle is closed exactly once; only the origin and id own memory
+        drop(unsafe { ptr::read(&board.origin) });
+        drop(unsafe { ptr::read(&board.id) });
+        check(unsafe { CgosBoardClose(board.handle) }, "CgosBoardClose")
     }
 
-    pub fn info(&self) -> BoardInfo {
-        let mut info: CGOSBOARDINFOA = unsafe { zeroed() };
-        info.dwSize = size_of::<CGOSBOARDINFOA>() as u32;
-        assert_ne!(unsafe { CgosBoardGetInfoA(self.handle, &mut info) }, 0);
-        info.into()
+    /// Checks through the board's own handle that it still reports the same identity, so no
+    /// additional handle is needed. Boards opened by index must also still be within the count
+    /// of their class.
+    pub fn is_present(&self) -> bool {
+        if let BoardOrigin::Index { class, index } = &self.origin {
+            if !matches!(Board::amount(*class), Ok(amount) if *index < amount) {
+                return false;
+            }
+        }
+        read_info(self.handle)
+            .map(BoardId::from)
+            .is_ok_and(|id| id == self.id)
     }
 
-    pub fn boot_count(&self) -> usize {
+    pub(crate) fn check_call(&self, result: u32, function: &'static str) -> CgosResult<()> {
+        check(result, function).map_err(|error| self.classify(error))
+    }
+
+    fn classify(&self, error: CgosError) -> CgosError {
+        if self.is_present() {
+            error
+        } else {
+            CgosError::BoardRemoved
+        }
+    }
+
+    pub fn name(&self) -> CgosResult<String> {
+        read_name(self.handle).map_err(|error| self.classify(error))
+    }
+
+    pub fn supports_class(&self, class: BoardClass) -> bool {
+        matches!(self.info(), Ok(info) if info.classes.contains(class))
+    }
+
+    /// Identifies the physical board; read once when the board is opened.
+    pub fn id(&self) -> &BoardId {
+        &self.id
+    }
+
+    pub fn info(&self) -> CgosResult<BoardInfo> {
+        read_info(self.handle).map_err(|error| self.classify(error))
+    }
+
+    pub fn boot_count(&self) -> CgosResult<usize> {
         let mut count = 0;
-        assert_ne!(
+        self.check_call(
             unsafe { CgosBoardGetBootCounter(self.handle, &mut count) },
-            0,
-        );
-        count as usize
+            "CgosBoardGetBootCounter",
+        )?;
+        Ok(count as usize)
     }
 
-    pub fn running_time(&self) -> Duration {
+    pub fn running_time(&self) -> CgosResult<Duration> {
         let mut hours = 0;
-        assert_ne!(
-            unsafe { CgosBoardGetBootCounter(self.handle, &mut hours) },
-            0,
-        );
-        Duration::from_secs(hours as u64 * 60 * 60)
+        self.check_call(
+            unsafe { CgosBoardGetRunningTimeMeter(self.handle, &mut hours) },
+            "CgosBoardGetRunningTimeMeter",
+        )?;
+        Ok(Duration::from_secs(hours as u64 * 60 * 60))
     }
 
-    pub fn get_number_of_temperatures(&self) -> usize {
-        Temperature::amount(self.handle)
+    pub fn get_option(&self, id: u32) -> CgosResult<u32> {
+        let mut value = 0;
+        self.check_call(
+            unsafe { CgosBoardGetOption(self.handle, id, &mut value) },
+            "CgosBoardGetOption",
+        )?;
+        Ok(value)
+    }
+
+    pub fn set_option(&self, id: u32, value: u32) -> CgosResult<()> {
+        self.check_call(
+            unsafe { CgosBoardSetOption(self.handle, id, value) },
+            "CgosBoardSetOption",
+        )
+    }
+
+    /// Reads the boot error log as raw bytes. If libcgos reports a log longer than the buffer,
+    /// the read is repeated with a buffer of the reported length.
+    pub fn boot_error_log(&self) -> CgosResult<BootErrorLog> {
+        let mut data = vec![0; 4096];
+        loop {
+            let mut log_type = 0;
+            let mut length = data.len() as u32;
+            self.check_call(
+                unsafe {
+                    CgosBoardGetBootErrorLog(
+                        self.handle,
+                        0,
+                        &mut log_type,
+                        data.as_mut_ptr(),
+                        &mut length,
+                    )
+                },
+                "CgosBoardGetBootErrorLog",
+            )?;
+            let length = length as usize;
+            if length > data.len() {
+                data.resize(length, 0);
+                continue;
+            }
+            data.truncate(length);
+            return Ok(BootErrorLog { log_type, data });
+        }
+    }
+
+    pub fn get_number_of_temperatures(&self) -> CgosResult<usize> {
+        cached(&self.counts.temperatures, || {
+            Temperature::amount(self.handle)
+        })
+        .map_err(|error| self.classify(error))
     }
 
     pub fn get_temperature(&'library self, index: usize) -> Temperature<'library> {
-        Temperature::new(self.handle, index)
+        self.get_number_of_temperatures()
+            .and_then(|amount| Temperature::new(self, index, amount))
+            .unwrap_or_else(|error| panic!("failed to get temperature {index}: {error}"))
+    }
+
+    pub fn try_get_temperature(&self, index: usize) -> Option<Temperature<'_>> {
+        self.get_number_of_temperatures()
+            .and_then(|amount| Temperature::new(self, index, amount))
+            .ok()
+    }
+
+    pub fn temperatures(&self) -> CgosResult<impl Iterator<Item = Temperature<'_>> + '_> {
+        let amount = self.get_number_of_temperatures()?;
+        Ok((0..amount).filter_map(move |index| Temperature::new(self, index, amount).ok()))
     }
 
-    pub fn get_number_of_fans(&self) -> usize {
-        Fan::amount(self.handle)
+    pub fn get_number_of_fans(&self) -> CgosResult<usize> {
+        cached(&self.counts.fans, || Fan::amount(self.handle)).map_err(|error| self.classify(error))
     }
 
     pub fn get_fan(&'library self, index: usize) -> Fan<'library> {
-        Fan::new(self.handle, index)
+        self.get_number_of_fans()
+            .and_then(|amount| Fan::new(self, index, amount))
+            .unwrap_or_else(|error| panic!("failed to get fan {index}: {error}"))
+    }
+
+    pub fn try_get_fan(&self, index: usize) -> Option<Fan<'_>> {
+        self.get_number_of_fans()
+            .and_then(|amount| Fan::new(self, index, amount))
+            .ok()
+    }
+
+    pub fn fans(&self) -> CgosResult<impl Iterator<Item = Fan<'_>> + '_> {
+        let amount = self.get_number_of_fans()?;
+        Ok((0..amount).filter_map(move |index| Fan::new(self, index, amount).ok()))
     }
 
-    pub fn get_number_of_storage_areas(&self, type_: StorageAreaType) -> usize {
-        StorageArea::amount(self.handle, type_)
+    pub fn get_number_of_voltages(&self) -> CgosResult<usize> {
+        cached(&self.counts.voltages, || Voltage::amount(self.handle))
+            .map_err(|error| self.classify(error))
+    }
+
+    pub fn get_voltage(&'library self, index: usize) -> Voltage<'library> {
+        self.get_number_of_voltages()
+            .and_then(|amount| Voltage::new(self, index, amount))
+            .unwrap_or_else(|error| panic!("failed to get voltage {index}: {error}"))
+    }
+
+    pub fn try_get_voltage(&self, index: usize) -> Option<Voltage<'_>> {
+        self.get_number_of_voltages()
+            .and_then(|amount| Voltage::new(self, index, amount))
+            .ok()
+    }
+
+    pub fn voltages(&self) -> CgosResult<impl Iterator<Item = Voltage<'_>> + '_> {
+        let amount = self.get_number_of_voltages()?;
+        Ok((0..amount).filter_map(move |index| Voltage::new(self, index, amount).ok()))
+    }
+
+    pub fn get_number_of_watchdogs(&self) -> CgosResult<usize> {
+        cached(&self.counts.watchdogs, || Watchdog::amount(self.handle))
+            .map_err(|error| self.classify(error))
+    }
+
+    pub fn get_watchdog(&'library self, index: usize) -> Watchdog<'library> {
+        self.get_number_of_watchdogs()
+            .and_then(|amount| Watchdog::new(self, index, amount))
+            .unwrap_or_else(|error| panic!("failed to get watchdog {index}: {error}"))
+    }
+
+    pub fn try_get_watchdog(&self, index: usize) -> Option<Watchdog<'_>> {
+        self.get_number_of_watchdogs()
+            .and_then(|amount| Watchdog::new(self, index, amount))
+            .ok()
+    }
+
+    pub fn watchdogs(&self) -> CgosResult<impl Iterator<Item = Watchdog<'_>> + '_> {
+        let amount = self.get_number_of_watchdogs()?;
+        Ok((0..amount).filter_map(move |index| Watchdog::new(self, index, amount).ok()))
+    }
+
+    pub fn get_number_of_vga_channels(&self) -> CgosResult<usize> {
+        cached(&self.counts.vga_channels, || Vga::amount(self.handle))
+            .map_err(|error| self.classify(error))
+    }
+
+    pub fn get_vga(&'library self, index: usize) -> Vga<'library> {
+        self.get_number_of_vga_channels()
+            .and_then(|amount| Vga::new(self, index, amount))
+            .unwrap_or_else(|error| panic!("failed to get VGA channel {index}: {error}"))
+    }
+
+    pub fn try_get_vga(&self, index: usize) -> Option<Vga<'_>> {
+        self.get_number_of_vga_channels()
+            .and_then(|amount| Vga::new(self, index, amount))
+            .ok()
+    }
+
+    pub fn vga_channels(&self) -> CgosResult<impl Iterator<Item = Vga<'_>> + '_> {
+        let amount = self.get_number_of_vga_channels()?;
+        Ok((0..amount).filter_map(move |index| Vga::new(self, index, amount).ok()))
+    }
+
+    pub fn get_number_of_io_units(&self) -> CgosResult<usize> {
+        cached(&self.counts.io_units, || Io::amount(self.handle))
+            .map_err(|error| self.classify(error))
+    }
+
+    pub fn get_io(&'library self, index: usize) -> Io<'library> {
+        self.get_number_of_io_units()
+            .and_then(|amount| Io::new(self, index, amount))
+            .unwrap_or_else(|error| panic!("failed to get I/O unit {index}: {error}"))
+    }
+
+    pub fn try_get_io(&self, index: usize) -> Option<Io<'_>> {
+        self.get_number_of_io_units()
+            .and_then(|amount| Io::new(self, index, amount))
+            .ok()
+    }
+
+    pub fn io_units(&self) -> CgosResult<impl Iterator<Item = Io<'_>> + '_> {
+        let amount = self.get_number_of_io_units()?;
+        Ok((0..amount).filter_map(move |index| Io::new(self, index, amount).ok()))
+    }
+
+    pub fn sensor_snapshot(&self) -> CgosResult<SensorSnapshot> {
+        Ok(SensorSnapshot {
+            temperatures: self
+                .temperatures()?
+                .map(|temperature| {
+                    let (value, status) = temperature.current()?;
+                    Ok((temperature.info()?, value, status))
+                })
+                .collect::<CgosResult<_>>()?,
+            fans: self
+                .fans()?
+                .map(|fan| {
+                    let (value, status) = fan.current()?;
+                    Ok((fan.info()?, value, status))
+                })
+                .collect::<CgosResult<_>>()?,
+            voltages: self
+                .voltages()?
+                .map(|voltage| {
+                    let (value, status) = voltage.current()?;
+                    Ok((voltage.info()?, value, status))
+                })
+                .collect::<CgosResult<_>>()?,
+        })
+    }
+
+    pub fn apply_fan_config(&self, config: &FanConfig) -> CgosResult<FanConfigReport> {
+        let amount = self.get_number_of_fans()?;
+        let mut report = FanConfigReport::default();
+        for (selector, limits) in &config.fans {
+            let mut fans = Vec::new();
+            for index in 0..amount {
+                if matches!(selector, FanSelector::Index(selected) if *selected != index) {
+                    continue;
+                }
+                let fan = self.get_fan(index);
+                let info = match fan.info() {
+                    Ok(info) => info,
+                    Err(error) => {
+                        fans.push((index, FanOutcome::Failed(error)));
+                        continue;
+                    }
+                };
+                if !selector.matches(index, info.type_) {
+                    continue;
+                }
+                let outcome = if !limits.fits(&info) {
+                    FanOutcome::OutOfRange
+                } else {
+                    match fan.set_limits(limits.apply_to(info)) {
+                        Ok(()) => FanOutcome::Configured,
+                        Err(error) => FanOutcome::Failed(error),
+                    }
+                };
+                fans.push((index, outcome));
+            }
+            report.selectors.push(SelectorReport {
+                selector: *selector,
+                fans,
+            });
+        }
+        Ok(report)
+    }
+
+    pub fn get_number_of_storage_areas(&self, type_: StorageAreaType) -> CgosResult<usize> {
+        StorageArea::amount(self.handle, type_).map_err(|error| self.classify(error))
     }
 
     pub fn get_storage_area_from_index(&'library self, index: usize) -> StorageArea<'library> {
-        StorageArea::from_index(self.handle, index)
+        StorageArea::from_index(self, index)
     }
 
     pub fn get_storage_area_from_type(
         &'library self,
         type_: StorageAreaType,
     ) -> StorageArea<'library> {
-        StorageArea::from_type(self.handle, type_)
+        StorageArea::from_type(self, type_)
+    }
+
+    pub fn storage_areas(&self) -> CgosResult<impl Iterator<Item = StorageArea<'_>> + '_> {
+        // areas of every type are counted by asking for the unknown type
+        let amount = self.get_number_of_storage_areas(StorageAreaType::UNKNOWN)?;
+        Ok((0..amount).map(move |index| StorageArea::from_index(self, index)))
+    }
+
+    pub fn find_storage_area(
+        &'library self,
+        type_: StorageAreaType,
+    ) -> Option<StorageArea<'library>> {
+        matches!(self.get_number_of_storage_areas(type_), Ok(amount) if amount > 0)
+            .then(|| self.get_storage_area_from_type(type_))
+    }
+
+    /// Locks every unlocked area that supports locking and returns the outcome per area index.
+    ///
+    /// Areas that are already locked or do not support locking are left out of the result.
+    pub fn lock_all_areas(&self, secret: &[u8]) -> CgosResult<Vec<(usize, CgosResult<()>)>> {
+        let amount = self.get_number_of_storage_areas(StorageAreaType::UNKNOWN)?;
+        Ok((0..amount)
+            .filter_map(|index| {
+                let area = self.get_storage_area_from_index(index);
+                (area.supports_locking() && !area.is_locked()).then(|| (index, area.lock(secret)))
+            })
+            .collect())
+    }
+}
+
+/// Equality and hashing of boards compare the physical board, not the handle: two boards opened
+/// independently for the same hardware are equal.
+impl<'library> PartialEq for Board<'library> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<'library> Eq for Board<'library> {}
+
+impl<'library> Hash for Board<'library> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
     }
 }
 
 impl<'library> Drop for Board<'library> {
     fn drop(&mut self) {
-        assert_ne!(unsafe { CgosBoardClose(self.handle) }, 0);
+        unsafe { CgosBoardClose(self.handle) };
     }
 }
 
+// keep boards movable across threads; a raw pointer field would silently drop `Send`
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<Board>();
+};
+
+fn read_name(handle: u32) -> CgosResult<String> {
+    Ok(read_raw_name(handle)?.to_string_lossy().into_owned())
+}
+
+/// The name exactly as libcgos reports it, for opening the board by name again.
+fn read_raw_name(handle: u32) -> CgosResult<CString> {
+    const SIZE: usize = 128;
+    let mut name = vec![0; SIZE];
+    check(
+        unsafe { CgosBoardGetNameA(handle, name.as_mut_ptr() as *mut i8, SIZE as u32) },
+        "CgosBoardGetNameA",
+    )?;
+    Ok(unsafe { CStr::from_ptr(name.as_ptr()) }.to_owned())
+}
+
+fn read_info(handle: u32) -> CgosResult<BoardInfo> {
+    let mut info: CGOSBOARDINFOA = unsafe { zeroed() };
+    info.dwSize = size_of::<CGOSBOARDINFOA>() as u32;
+    check(
+        unsafe { CgosBoardGetInfoA(handle, &mut info) },
+        "CgosBoardGetInfoA",
+    )?;
+    Ok(info.into())
+}
+
+fn open_handle(origin: &BoardOrigin) -> CgosResult<u32> {
+    let mut handle = Default::default();
+    match origin {
+        BoardOrigin::Index { class, index } => check(
+            unsafe {
+                CgosBoardOpen(
+                    class.to_raw(),
+                    (*index).try_into().unwrap(),
+                    FLAGS,
+                    &mut handle,
+                )
+            },
+            "CgosBoardOpen",
+        )?,
+        BoardOrigin::Name(name) => check(
+            unsafe { CgosBoardOpenByNameA(name.as_ptr(), &mut handle) },
+            "CgosBoardOpenByNameA",
+        )?,
+    }
+    Ok(handle)
+}
+
 bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct BoardClass: u32 {
-        const ALL = 0;
         const CPU = CGOS_BOARD_CLASS_CPU;
         const VGA = CGOS_BOARD_CLASS_VGA;
         const IO = CGOS_BOARD_CLASS_IO;
+        const ALL = Self::CPU.bits | Self::VGA.bits | Self::IO.bits;
     }
 }
 
+impl BoardClass {
+    /// libcgos selects boards of any class with 0, so `ALL` is translated at the FFI boundary
+    /// instead of being stored as an empty set that every flag set would contain.
+    pub(crate) fn to_raw(self) -> u32 {
+        if self == BoardClass::ALL {
+            0
+        } else {
+            self.bits
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug)]
 pub struct BoardInfo {
     pub board: String,
@@ -151,6 +642,7 @@ pub struct BoardInfo {
     pub serial_number: String,
     pub product_revision: String,
     pub system_bios_revision: u16,
+    pub bios_interface_revision: u16,
     pub bios_interface_build_revision: u16,
     pub classes: BoardClass,
     pub primary_class: BoardClass,
@@ -159,37 +651,133 @@ pub struct BoardInfo {
     pub european_article_number: String,
 }
 
+impl BoardInfo {
+    pub fn is_cpu(&self) -> bool {
+        self.classes.contains(BoardClass::CPU)
+    }
+
+    pub fn is_vga(&self) -> bool {
+        self.classes.contains(BoardClass::VGA)
+    }
+
+    pub fn is_io(&self) -> bool {
+        self.classes.contains(BoardClass::IO)
+    }
+
+    pub fn class_list(&self) -> Vec<BoardClass> {
+        [BoardClass::CPU, BoardClass::VGA, BoardClass::IO]
+            .into_iter()
+            .filter(|class| self.classes.contains(*class))
+            .collect()
+    }
+
+    pub fn bios_revision(&self) -> BiosRevision {
+        self.system_bios_revision.into()
+    }
+
+    pub fn bios_interface_version(&self) -> BiosRevision {
+        self.bios_interface_revision.into()
+    }
+
+    pub fn bios_version_string(&self) -> String {
+        self.bios_revision().to_string()
+    }
+
+    #[cfg(feature = "time")]
+    pub fn manufacturing_summary(&self) -> ManufacturingSummary {
+        ManufacturingSummary {
+            manufacturer: self.manufacturer.clone(),
+            manufacturer_sub: self.manufacturer_sub,
+            manufacturing_date: self.manufacturing_date.date(),
+            last_repair_date: self.last_repair_date.date(),
+            repair_counter: self.repair_counter,
+        }
+    }
+}
+
+/// Dates are `None` when the board reports a zeroed or otherwise invalid date.
+#[cfg(feature = "time")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManufacturingSummary {
+    pub manufacturer: String,
+    pub manufacturer_sub: u32,
+    pub manufacturing_date: Option<Date>,
+    pub last_repair_date: Option<Date>,
+    pub repair_counter: u32,
+}
+
+/// A revision word split into its high byte as major and its low byte as minor version.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BiosRevision {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl From<u16> for BiosRevision {
+    fn from(revision: u16) -> Self {
+        Self {
+            major: (revision >> 8) as u8,
+            minor: revision as u8,
+        }
+    }
+}
+
+impl Display for BiosRevision {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl Display for BoardInfo {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        writeln!(formatter, "board: {} {}", self.board, self.board_sub)?;
+        writeln!(formatter, "manufacturer: {}", self.manufacturer)?;
+        writeln!(formatter, "serial number: {}", self.serial_number)?;
+        writeln!(formatter, "part number: {}", self.part_number)?;
+        writeln!(formatter, "product revision: {}", self.product_revision)?;
+        writeln!(formatter, "BIOS revision: {}", self.bios_revision())?;
+        writeln!(
+            formatter,
+            "BIOS interface revision: {} (build {})",
+            self.bios_interface_version(),
+            self.bios_interface_build_revision
+        )?;
+        writeln!(formatter, "manufacturing date: {}", self.manufacturing_date)?;
+        write!(
+            formatter,
+            "last repair date: {} ({} repairs)",
+            self.last_repair_date, self.repair_counter
+        )
+    }
+}
+
+/// Strings are decoded lossily: bytes that are not valid UTF-8 become U+FFFD.
 impl From<CGOSBOARDINFOA> for BoardInfo {
     fn from(info: CGOSBOARDINFOA) -> Self {
         let board = unsafe { CStr::from_ptr(info.szBoard.as_ptr()) }
-            .to_str()
-            .unwrap()
-            .to_string();
+            .to_string_lossy()
+            .into_owned();
         let board_sub = unsafe { CStr::from_ptr(info.szBoardSub.as_ptr()) }
-            .to_str()
-            .unwrap()
-            .to_string();
+            .to_string_lossy()
+            .into_owned();
         let manufacturer = unsafe { CStr::from_ptr(info.szManufacturer.as_ptr()) }
-            .to_str()
-            .unwrap()
-            .to_string();
+            .to_string_lossy()
+            .into_owned();
         let serial_number = unsafe { CStr::from_ptr(info.szSerialNumber.as_ptr()) }
-            .to_str()
-            .unwrap()
-            .to_string();
+            .to_string_lossy()
+            .into_owned();
         let product_revision = {
             let major = ((info.wProductRevision & 0xff00) >> 8) as u8 as char;
             let minor = (info.wProductRevision & 0xff) as u8 as char;
             format!("{major}.{minor}")
         };
         let part_number = unsafe { CStr::from_ptr(info.szPartNumber.as_ptr()) }
-            .to_str()
-            .unwrap()
-            .to_string();
+            .to_string_lossy()
+            .into_owned();
         let european_article_number = unsafe { CStr::from_ptr(info.szEAN.as_ptr()) }
-            .to_str()
-            .unwrap()
-            .to_string();
+            .to_string_lossy()
+            .into_owned();
         Self {
             board,
             board_sub,
@@ -199,7 +787,8 @@ impl From<CGOSBOARDINFOA> for BoardInfo {
             last_repair_date: info.stLastRepairDate.into(),
             serial_number,
             product_revision,
-            system_bios_revision: info.wBiosInterfaceRevision,
+            system_bios_revision: info.wSystemBiosRevision,
+            bios_interface_revision: info.wBiosInterfaceRevision,
             bios_interface_build_revision: info.wBiosInterfaceBuildRevision,
             classes: BoardClass::from_bits_truncate(info.dwClasses),
             primary_class: BoardClass::from_bits_truncate(info.dwPrimaryClass),
@@ -210,6 +799,41 @@ impl From<CGOSBOARDINFOA> for BoardInfo {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BoardId {
+    pub name: String,
+    pub serial_number: String,
+}
+
+impl From<BoardInfo> for BoardId {
+    fn from(info: BoardInfo) -> Self {
+        Self {
+            name: info.board,
+            serial_number: info.serial_number,
+        }
+    }
+}
+
+/// Every sensor of the board with its info, current value and current status.
+#[derive(Clone, Debug, Default)]
+pub struct SensorSnapshot {
+    pub temperatures: Vec<(TemperatureInfo, Celsius, Status)>,
+    pub fans: Vec<(FanInfo, Rpm, Status)>,
+    pub voltages: Vec<(VoltageInfo, Volt, Status)>,
+}
+
+/// Congatec does not document the log layout, so the log is kept undecoded together with the
+/// type libcgos reports for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BootErrorLog {
+    pub log_type: u32,
+    pub data: Vec<u8>,
+}
+
+/// Only used for the dates stored in the board information. libcgos does not expose the
+/// real-time clock, so the board time cannot be read or set through this crate; use the OS
+/// clock instead.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub struct BoardTime {
     pub year: u16,
@@ -222,6 +846,94 @@ pub struct BoardTime {
     pub millisecond: u16,
 }
 
+impl BoardTime {
+    pub fn is_valid(&self) -> bool {
+        self.year != 0
+            && (1..=12).contains(&self.month)
+            && (1..=self.days_in_month()).contains(&self.day)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+
+    fn days_in_month(&self) -> u16 {
+        let is_leap_year = self.year.is_multiple_of(4)
+            && (!self.year.is_multiple_of(100) || self.year.is_multiple_of(400));
+        match self.month {
+            2 if is_leap_year => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    #[cfg(feature = "time")]
+    pub fn date(&self) -> Option<Date> {
+        if !self.is_valid() {
+            return None;
+        }
+        let month = Month::try_from(self.month as u8).ok()?;
+        Date::from_calendar_date(self.year as i32, month, self.day as u8).ok()
+    }
+}
+
+/// `day_of_week` is ignored, libcgos sometimes leaves it zero.
+#[cfg(feature = "time")]
+impl TryFrom<BoardTime> for PrimitiveDateTime {
+    type Error = CgosError;
+
+    fn try_from(board_time: BoardTime) -> CgosResult<Self> {
+        let date = board_time.date().ok_or(CgosError::InvalidTime)?;
+        let time = Time::from_hms_milli(
+            board_time.hour as u8,
+            board_time.minute as u8,
+            board_time.second as u8,
+            board_time.millisecond,
+        )
+        .map_err(|_| CgosError::InvalidTime)?;
+        Ok(PrimitiveDateTime::new(date, time))
+    }
+}
+
+/// `day_of_week` is ignored, libcgos sometimes leaves it zero.
+#[cfg(feature = "chrono")]
+impl TryFrom<BoardTime> for NaiveDateTime {
+    type Error = CgosError;
+
+    fn try_from(board_time: BoardTime) -> CgosResult<Self> {
+        if !board_time.is_valid() {
+            return Err(CgosError::InvalidTime);
+        }
+        let date = NaiveDate::from_ymd_opt(
+            board_time.year as i32,
+            board_time.month as u32,
+            board_time.day as u32,
+        )
+        .ok_or(CgosError::InvalidTime)?;
+        let time = NaiveTime::from_hms_milli_opt(
+            board_time.hour as u32,
+            board_time.minute as u32,
+            board_time.second as u32,
+            board_time.millisecond as u32,
+        )
+        .ok_or(CgosError::InvalidTime)?;
+        Ok(NaiveDateTime::new(date, time))
+    }
+}
+
+impl Display for BoardTime {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if !self.is_valid() {
+            return write!(formatter, "unknown");
+        }
+        write!(
+            formatter,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
 impl From<CGOSTIME> for BoardTime {
     fn from(time: CGOSTIME) -> Self {
         Self {
@@ -236,3 +948,156 @@ impl From<CGOSTIME> for BoardTime {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{mem::zeroed, time::Duration};
+
+    use super::{Board, BoardClass, BoardInfo};
+    use crate::{
+        bindings::{
+            mock, CGOSBOARDINFOA, CGOS_BOARD_CLASS_CPU, CGOS_BOARD_CLASS_IO, CGOS_BOARD_CLASS_VGA,
+        },
+        error::CgosError,
+    };
+
+    const CLASSES: [(BoardClass, u32); 3] = [
+        (BoardClass::CPU, CGOS_BOARD_CLASS_CPU),
+        (BoardClass::VGA, CGOS_BOARD_CLASS_VGA),
+        (BoardClass::IO, CGOS_BOARD_CLASS_IO),
+    ];
+
+    #[test]
+    fn counts_are_cached_once_nonzero_until_reconnect() {
+        let mut board = mock::open_board();
+        assert_eq!(board.get_number_of_temperatures().unwrap(), 0);
+
+        mock::set_value("CgosTemperatureCount", 2);
+        assert_eq!(board.get_number_of_temperatures().unwrap(), 2);
+        mock::set_value("CgosTemperatureCount", 3);
+        assert_eq!(board.get_number_of_temperatures().unwrap(), 2);
+
+        board.reconnect().unwrap();
+        assert_eq!(board.get_number_of_temperatures().unwrap(), 3);
+    }
+
+    #[test]
+    fn failures_of_present_board_keep_their_error() {
+        let board = mock::open_board();
+        mock::fail("CgosBoardGetBootCounter", 0x30);
+
+        mock::assert_failed(board.boot_count(), "CgosBoardGetBootCounter", 0x30);
+        assert_eq!(
+            mock::calls()
+                .iter()
+                .filter(|call| call.starts_with("CgosBoardOpen"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn board_opened_by_index_is_removed_when_count_shrinks() {
+        let board = mock::open_board();
+        mock::fail("CgosBoardGetBootCounter", 0x30);
+        mock::set_value("CgosBoardCount", 0);
+
+        assert!(matches!(board.boot_count(), Err(CgosError::BoardRemoved)));
+    }
+
+    #[test]
+    fn board_opened_by_index_is_removed_when_swapped() {
+        let board = mock::open_board();
+        mock::fail("CgosBoardGetBootCounter", 0x30);
+        mock::set_serial_number("other");
+
+        assert!(matches!(board.boot_count(), Err(CgosError::BoardRemoved)));
+    }
+
+    #[test]
+    fn board_opened_by_name_is_checked_without_another_handle() {
+        let board = Board::try_from_name("board").unwrap();
+        mock::fail("CgosBoardGetBootCounter", 0x30);
+
+        mock::assert_failed(board.boot_count(), "CgosBoardGetBootCounter", 0x30);
+        mock::set_serial_number("other");
+        assert!(matches!(board.boot_count(), Err(CgosError::BoardRemoved)));
+        assert_eq!(
+            mock::calls()
+                .iter()
+                .filter(|call| call.starts_with("CgosBoardOpen"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn reconnect_rejects_a_different_board() {
+        let mut board = mock::open_board();
+        mock::set_serial_number("other");
+
+        assert!(matches!(board.reconnect(), Err(CgosError::BoardChanged)));
+        // only the new handle is closed, the current one stays in use
+        assert_eq!(
+            mock::calls()
+                .iter()
+                .filter(|call| **call == "CgosBoardClose")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn real_classes_round_trip() {
+        for (class, raw) in CLASSES {
+            assert_eq!(class.to_raw(), raw);
+            let mut info: CGOSBOARDINFOA = unsafe { zeroed() };
+            info.dwClasses = raw;
+            info.dwPrimaryClass = raw;
+            let info = BoardInfo::from(info);
+            assert_eq!(info.classes, class);
+            assert_eq!(info.primary_class, class);
+            assert_eq!(info.class_list(), [class]);
+        }
+    }
+
+    #[test]
+    fn supports_only_reported_classes() {
+        let board = mock::open_board();
+        for (class, raw) in CLASSES {
+            mock::set_value("CgosBoardGetInfoA", raw);
+            for (other, _) in CLASSES {
+                assert_eq!(board.supports_class(other), other == class);
+            }
+            assert!(!board.supports_class(BoardClass::ALL));
+        }
+    }
+
+    #[test]
+    fn running_time_and_boot_count_read_distinct_counters() {
+        let board = mock::open_board();
+        mock::set_value("CgosBoardGetBootCounter", 7);
+        mock::set_value("CgosBoardGetRunningTimeMeter", 3);
+
+        assert_eq!(board.boot_count().unwrap(), 7);
+        assert_eq!(
+            board.running_time().unwrap(),
+            Duration::from_secs(3 * 60 * 60)
+        );
+        let calls = mock::calls();
+        assert_eq!(
+            calls
+                .iter()
+                .filter(|function| **function == "CgosBoardGetBootCounter")
+                .count(),
+            1
+        );
+        assert_eq!(
+            calls
+                .iter()
+                .filter(|function| **function == "CgosBoardGetRunningTimeMeter")
+                .count(),
+            1
+        );
+    }
+}
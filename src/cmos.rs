@@ -0,0 +1,38 @@
+use crate::{
+    error::CgosResult,
+    storage_area::{StorageArea, StorageAreaType},
+};
+
+pub struct Cmos<'library> {
+    area: StorageArea<'library>,
+}
+
+impl<'library> Cmos<'library> {
+    pub fn new(area: StorageArea<'library>) -> Option<Self> {
+        (area.type_() == StorageAreaType::CMOS).then_some(Self { area })
+    }
+
+    pub fn read_u8(&self, offset: usize) -> CgosResult<u8> {
+        let mut data = [0; 1];
+        self.area.read(offset, &mut data)?;
+        Ok(data[0])
+    }
+
+    pub fn write_u8(&self, offset: usize, value: u8) -> CgosResult<()> {
+        self.area.write(offset, &[value])
+    }
+
+    pub fn read_le_u32(&self, offset: usize) -> CgosResult<u32> {
+        let mut data = [0; 4];
+        self.area.read(offset, &mut data)?;
+        Ok(u32::from_le_bytes(data))
+    }
+
+    pub fn write_le_u32(&self, offset: usize, value: u32) -> CgosResult<()> {
+        self.area.write(offset, &value.to_le_bytes())
+    }
+
+    pub fn into_inner(self) -> StorageArea<'library> {
+        self.area
+    }
+}
@@ -1,31 +1,248 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    sync::{Mutex, PoisonError},
+};
+
 use crate::{
-    bindings::{CgosLibInitialize, CgosLibUninitialize},
+    bindings::{
+        CgosLibGetDrvVersion, CgosLibGetVersion, CgosLibInitialize, CgosLibIsAvailable,
+        CgosLibUninitialize,
+    },
     board::{Board, BoardClass},
+    error::{check, CgosResult},
 };
 
-pub struct Congatec {}
+// libcgos is initialized by the first and uninitialized by the last live `Congatec`
+static INSTANCES: Mutex<usize> = Mutex::new(0);
+
+/// libcgos has no API to configure its own log or debug level, so library logging cannot be
+/// changed from here.
+pub struct Congatec {
+    // only `new` may create instances, since every instance is counted in `INSTANCES`
+    _private: (),
+}
 
 impl Congatec {
-    pub fn new() -> Self {
-        assert_ne!(unsafe { CgosLibInitialize() }, 0);
-        Self {}
+    pub fn new() -> CgosResult<Self> {
+        let mut instances = INSTANCES.lock().unwrap_or_else(PoisonError::into_inner);
+        if *instances == 0 {
+            check(unsafe { CgosLibInitialize() }, "CgosLibInitialize")?;
+        }
+        *instances += 1;
+        Ok(Self { _private: () })
+    }
+
+    /// Keeps the library initialized for the rest of the process, so `CgosLibUninitialize` is
+    /// never called. libcgos then holds on to its driver connection and internal allocations
+    /// until the process exits, which is the intended trade-off for long-running daemons.
+    pub fn leak(self) -> &'static Congatec {
+        Box::leak(Box::new(self))
+    }
+
+    pub fn library_version(&self) -> Version {
+        unsafe { CgosLibGetVersion() }.into()
     }
 
-    pub fn get_number_of_boards(&self, class: BoardClass) -> usize {
+    pub fn driver_version(&self) -> Version {
+        unsafe { CgosLibGetDrvVersion() }.into()
+    }
+
+    pub fn get_number_of_boards(&self, class: BoardClass) -> CgosResult<usize> {
         Board::amount(class)
     }
 
+    /// libcgos only supports a limited number of simultaneously open board handles and offers
+    /// no call to query that limit; opening fails once the driver runs out of handles. The limit
+    /// has been observed to be 16 open handles, with the 17th `CgosBoardOpen` failing. The board
+    /// and libcgos version of that observation were not recorded, so treat it as a guideline.
     pub fn get_board<'library>(&'library self, class: BoardClass, index: usize) -> Board<'library> {
-        Board::new(class, index)
+        Board::try_new(class, index).unwrap_or_else(|error| panic!("failed to open board: {error}"))
+    }
+
+    pub fn try_get_board(&self, class: BoardClass, index: usize) -> Option<Board<'_>> {
+        Board::try_new(class, index).ok()
     }
 
     pub fn get_board_from_name<'library>(&'library self, name: &str) -> Board<'library> {
-        Board::from_name(name)
+        self.try_get_board_from_name(name)
+            .expect("failed to open board")
+    }
+
+    pub fn try_get_board_from_name(&self, name: &str) -> Option<Board<'_>> {
+        Board::try_from_name(name)
+    }
+
+    /// Boards that disappear or fail to open during iteration are yielded as errors.
+    pub fn boards(
+        &self,
+        class: BoardClass,
+    ) -> CgosResult<impl Iterator<Item = CgosResult<Board<'_>>> + '_> {
+        let amount = self.get_number_of_boards(class)?;
+        Ok((0..amount).map(move |index| Board::try_new(class, index)))
+    }
+
+    pub fn board_names(&self, class: BoardClass) -> Vec<String> {
+        let amount = self.get_number_of_boards(class).unwrap_or(0);
+        (0..amount)
+            .filter_map(|index| self.try_get_board(class, index)?.name().ok())
+            .collect()
+    }
+
+    pub fn unique_boards(&self) -> CgosResult<Vec<Board<'_>>> {
+        // boards belonging to several classes are enumerated once per class, but boards without
+        // a serial number cannot be told apart by their name alone and are all kept
+        let mut identities = HashSet::new();
+        let mut boards = Vec::new();
+        for class in [BoardClass::CPU, BoardClass::VGA, BoardClass::IO] {
+            for index in 0..self.get_number_of_boards(class)? {
+                let board = Board::try_new(class, index)?;
+                if board.id().serial_number.is_empty() || identities.insert(board.id().clone()) {
+                    boards.push(board);
+                }
+            }
+        }
+        Ok(boards)
+    }
+
+    pub fn is_stub(&self) -> bool {
+        // a functional driver reports at least the CPU board it is running on
+        !matches!(self.get_number_of_boards(BoardClass::CPU), Ok(amount) if amount > 0)
     }
 }
 
 impl Drop for Congatec {
     fn drop(&mut self) {
-        assert_ne!(unsafe { CgosLibUninitialize() }, 0);
+        let mut instances = INSTANCES.lock().unwrap_or_else(PoisonError::into_inner);
+        *instances -= 1;
+        if *instances == 0 {
+            unsafe { CgosLibUninitialize() };
+        }
+    }
+}
+
+/// Safe to call before any `Congatec` exists, e.g. to fall back to a simulated backend on
+/// machines without the CGOS driver.
+pub fn is_library_available() -> bool {
+    unsafe { CgosLibIsAvailable() != 0 }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u8,
+    pub minor: u8,
+    pub build: u16,
+}
+
+impl From<u32> for Version {
+    fn from(version: u32) -> Self {
+        Self {
+            major: (version >> 24) as u8,
+            minor: (version >> 16) as u8,
+            build: version as u16,
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}.{}.{}", self.major, self.minor, self.build)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Mutex, MutexGuard, PoisonError};
+
+    use super::{Congatec, INSTANCES};
+    use crate::{bindings::mock, error::CgosError};
+
+    // the instance count is process-wide, so tests creating instances must not overlap
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    fn serialize() -> MutexGuard<'static, ()> {
+        SERIAL.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn instances() -> usize {
+        *INSTANCES.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[test]
+    fn failed_initialization_returns_error() {
+        let _serial = serialize();
+        mock::fail("CgosLibInitialize", 0x42);
+
+        assert!(matches!(
+            Congatec::new(),
+            Err(CgosError::CallFailed {
+                function: "CgosLibInitialize",
+                last_error: 0x42,
+            })
+        ));
+        assert_eq!(instances(), 0);
+        assert!(!mock::calls().contains(&"CgosLibUninitialize"));
+    }
+
+    fn count(function: &str) -> usize {
+        mock::calls()
+            .into_iter()
+            .filter(|call| *call == function)
+            .count()
+    }
+
+    #[test]
+    fn library_is_initialized_once_for_overlapping_instances() {
+        let _serial = serialize();
+
+        let first = Congatec::new().unwrap();
+        let second = Congatec::new().unwrap();
+        assert_eq!(instances(), 2);
+        assert_eq!(count("CgosLibInitialize"), 1);
+
+        drop(first);
+        assert_eq!(instances(), 1);
+        assert_eq!(count("CgosLibUninitialize"), 0);
+
+        drop(second);
+        assert_eq!(instances(), 0);
+        assert_eq!(count("CgosLibUninitialize"), 1);
+    }
+
+    #[test]
+    fn unique_boards_merges_boards_by_serial_number() {
+        let _serial = serialize();
+        let congatec = Congatec::new().unwrap();
+        mock::set_value("CgosBoardCount", 2);
+        mock::set_serial_number("12345");
+
+        assert_eq!(congatec.unique_boards().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn unique_boards_keeps_boards_without_serial_number() {
+        let _serial = serialize();
+        let congatec = Congatec::new().unwrap();
+        mock::set_value("CgosBoardCount", 2);
+
+        assert_eq!(congatec.unique_boards().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn library_is_initialized_again_after_last_drop() {
+        let _serial = serialize();
+
+        drop(Congatec::new().unwrap());
+        drop(Congatec::new().unwrap());
+
+        assert_eq!(
+            mock::calls(),
+            [
+                "CgosLibInitialize",
+                "CgosLibUninitialize",
+                "CgosLibInitialize",
+                "CgosLibUninitialize",
+            ]
+        );
     }
 }
@@ -0,0 +1,46 @@
+const BLOCK_SIZE: usize = 128;
+const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+const PREFERRED_TIMING_OFFSET: usize = 54;
+
+#[derive(Clone, Debug)]
+pub struct Edid {
+    pub manufacturer_id: String,
+    pub product_code: u16,
+    pub serial_number: u32,
+    pub preferred_resolution: Option<(u32, u32)>,
+    pub extension_blocks: u8,
+}
+
+impl Edid {
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let block = bytes.get(..BLOCK_SIZE)?;
+        if block[..HEADER.len()] != HEADER {
+            return None;
+        }
+        if block.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+            return None;
+        }
+        let manufacturer = u16::from_be_bytes([block[8], block[9]]);
+        let manufacturer_id = [10, 5, 0]
+            .iter()
+            .map(|shift| match (manufacturer >> shift) & 0x1f {
+                letter @ 1..=26 => (b'A' + letter as u8 - 1) as char,
+                _ => '?',
+            })
+            .collect();
+        let timing = &block[PREFERRED_TIMING_OFFSET..PREFERRED_TIMING_OFFSET + 18];
+        let pixel_clock = u16::from_le_bytes([timing[0], timing[1]]);
+        let preferred_resolution = (pixel_clock != 0).then(|| {
+            let width = timing[2] as u32 | ((timing[4] as u32 & 0xf0) << 4);
+            let height = timing[5] as u32 | ((timing[7] as u32 & 0xf0) << 4);
+            (width, height)
+        });
+        Some(Self {
+            manufacturer_id,
+            product_code: u16::from_le_bytes([block[10], block[11]]),
+            serial_number: u32::from_le_bytes([block[12], block[13], block[14], block[15]]),
+            preferred_resolution,
+            extension_blocks: block[126],
+        })
+    }
+}
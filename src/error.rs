@@ -0,0 +1,128 @@
+use std::{io, time::Duration};
+
+use thiserror::Error;
+
+use crate::bindings::CgosLibGetLastError;
+
+#[derive(Debug, Error)]
+pub enum CgosError {
+    #[error("{function} failed with last error {last_error:#x}")]
+    CallFailed {
+        function: &'static str,
+        last_error: u32,
+    },
+    #[error("{function} returned zero while last error {last_error:#x} was already set")]
+    AmbiguousCount {
+        function: &'static str,
+        last_error: u32,
+    },
+    #[error("board is no longer present")]
+    BoardRemoved,
+    #[error("a different board was found where the board was opened")]
+    BoardChanged,
+    #[error("{what} {value} is out of range (limit {limit})")]
+    OutOfRange {
+        what: &'static str,
+        value: usize,
+        limit: usize,
+    },
+    #[error("{value} is not aligned to {alignment}")]
+    Unaligned { value: usize, alignment: usize },
+    #[error("erasing {length} bytes at offset {offset} failed")]
+    EraseFailed { offset: usize, length: usize },
+    #[error("operation timed out after {0:?}")]
+    Timeout(Duration),
+    #[error("verification failed after writing {length} bytes at offset {offset}")]
+    VerificationFailed { offset: usize, length: usize },
+    #[error("{error}, and restoring the previous contents failed: {rollback}")]
+    RollbackFailed {
+        error: Box<CgosError>,
+        rollback: Box<CgosError>,
+    },
+    #[error("{0} is not supported")]
+    Unsupported(&'static str),
+    #[error("board time is zeroed or out of range")]
+    InvalidTime,
+    #[error("storage area is locked")]
+    Locked,
+}
+
+pub type CgosResult<T> = Result<T, CgosError>;
+
+impl From<CgosError> for io::Error {
+    fn from(error: CgosError) -> Self {
+        io::Error::other(error)
+    }
+}
+
+pub(crate) fn last_error() -> u32 {
+    unsafe { CgosLibGetLastError() }
+}
+
+pub(crate) fn check(result: u32, function: &'static str) -> CgosResult<()> {
+    if result == 0 {
+        return Err(CgosError::CallFailed {
+            function,
+            last_error: last_error(),
+        });
+    }
+    Ok(())
+}
+
+/// Runs a `Cgos*Count` call and tells a genuine count of zero apart from a failure.
+///
+/// The counting functions report both as zero, and libcgos has no way to reset its last error. A
+/// zero with a last error that changed during the call is a failure, and a zero while the last
+/// error is still zero is a genuine count. A zero while an earlier nonzero error is still set
+/// cannot be told apart from the same failure happening again, so it is reported as ambiguous.
+pub(crate) fn check_amount(
+    count: impl FnOnce() -> u32,
+    function: &'static str,
+) -> CgosResult<usize> {
+    let previous_error = last_error();
+    let amount = count();
+    if amount == 0 {
+        let last_error = last_error();
+        if last_error != previous_error {
+            return Err(CgosError::CallFailed {
+                function,
+                last_error,
+            });
+        }
+        if last_error != 0 {
+            return Err(CgosError::AmbiguousCount {
+                function,
+                last_error,
+            });
+        }
+    }
+    Ok(amount as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CgosError;
+    use crate::{
+        bindings::mock,
+        board::{Board, BoardClass},
+    };
+
+    #[test]
+    fn zero_count_without_error_is_genuine() {
+        assert_eq!(Board::amount(BoardClass::CPU).unwrap(), 0);
+    }
+
+    #[test]
+    fn repeated_count_failure_with_same_error_is_not_a_zero_count() {
+        mock::fail("CgosBoardCount", 0x20);
+
+        mock::assert_failed(Board::amount(BoardClass::CPU), "CgosBoardCount", 0x20);
+        assert!(matches!(
+            Board::amount(BoardClass::CPU),
+            Err(CgosError::AmbiguousCount {
+                function: "CgosBoardCount",
+                last_error: 0x20,
+            })
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
-    marker::PhantomData,
     mem::{size_of, zeroed},
+    ops::RangeInclusive,
 };
 
 use crate::{
@@ -10,57 +10,91 @@ use crate::{
         CGOS_TEMP_CHIPSETS, CGOS_TEMP_CPU, CGOS_TEMP_ENV, CGOS_TEMP_OTHER, CGOS_TEMP_TOPDIMM_ENV,
         CGOS_TEMP_VIDEO,
     },
+    board::Board,
+    error::{check_amount, CgosError, CgosResult},
     status::Status,
+    temperature::percent_of_range,
+    units::Rpm,
 };
 
+/// Which temperature zones drive a fan's automatic control is decided by the board firmware;
+/// libcgos does not expose that mapping.
 pub struct Fan<'library> {
-    handle: u32,
+    board: &'library Board<'library>,
     index: u32,
-    _library_lifetime: PhantomData<&'library ()>,
 }
 
 impl<'library> Fan<'library> {
-    pub(crate) fn amount(handle: u32) -> usize {
-        unsafe { CgosFanCount(handle) as usize }
+    pub(crate) fn amount(handle: u32) -> CgosResult<usize> {
+        check_amount(|| unsafe { CgosFanCount(handle) }, "CgosFanCount")
     }
 
-    pub(crate) fn new(handle: u32, index: usize) -> Fan<'library> {
-        Self {
-            handle,
-            index: index.try_into().unwrap(),
-            _library_lifetime: PhantomData,
+    pub(crate) fn new(
+        board: &'library Board<'library>,
+        index: usize,
+        amount: usize,
+    ) -> CgosResult<Fan<'library>> {
+        if index >= amount {
+            return Err(CgosError::OutOfRange {
+                what: "fan index",
+                value: index,
+                limit: amount,
+            });
         }
+        Ok(Self {
+            board,
+            index: index.try_into().unwrap(),
+        })
     }
 
-    pub fn info(&self) -> FanInfo {
+    pub fn info(&self) -> CgosResult<FanInfo> {
         let mut info: CGOSFANINFO = unsafe { zeroed() };
         info.dwSize = size_of::<CGOSFANINFO>() as u32;
-        assert_ne!(
-            unsafe { CgosFanGetInfo(self.handle, self.index, &mut info) },
-            0
-        );
-        info.into()
+        self.board.check_call(
+            unsafe { CgosFanGetInfo(self.board.raw_handle(), self.index, &mut info) },
+            "CgosFanGetInfo",
+        )?;
+        Ok(info.into())
     }
 
-    pub fn current(&self) -> (i32, Status) {
+    pub fn current(&self) -> CgosResult<(Rpm, Status)> {
         let mut value = 0;
         let mut flags = 0;
-        assert_ne!(
-            unsafe { CgosFanGetCurrent(self.handle, self.index, &mut value, &mut flags) },
-            0,
-        );
-        (value as i32, Status::from_bits_truncate(flags))
+        self.board.check_call(
+            unsafe {
+                CgosFanGetCurrent(self.board.raw_handle(), self.index, &mut value, &mut flags)
+            },
+            "CgosFanGetCurrent",
+        )?;
+        Ok((Rpm(value as i32), Status::from_bits_truncate(flags)))
+    }
+
+    pub fn current_if_active(&self) -> CgosResult<Option<(Rpm, Status)>> {
+        let (value, status) = self.current()?;
+        Ok(status.contains(Status::ACTIVE).then_some((value, status)))
     }
 
-    pub fn set_limits(&self, info: FanInfo) {
+    pub fn set_limits(&self, info: FanInfo) -> CgosResult<()> {
         let mut info = info.into();
-        assert_ne!(
-            unsafe { CgosFanSetLimits(self.handle, self.index, &mut info) },
-            0,
-        );
+        self.board.check_call(
+            unsafe { CgosFanSetLimits(self.board.raw_handle(), self.index, &mut info) },
+            "CgosFanSetLimits",
+        )
+    }
+
+    pub fn is_stalled(&self) -> CgosResult<bool> {
+        let (speed, status) = self.current()?;
+        // CGOS offers no way to read the commanded output, so a fan counts as driven whenever
+        // its minimum output is above zero
+        Ok(status.contains(Status::BROKEN) || (speed == Rpm(0) && self.info()?.out_minimum > 0))
+    }
+
+    pub fn label(&self) -> CgosResult<String> {
+        Ok(format!("fan_{}_{}", self.info()?.type_.name(), self.index))
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub struct FanInfo {
     pub type_: FanType,
@@ -77,6 +111,18 @@ pub struct FanInfo {
     pub out_maximum: i32,
 }
 
+impl FanInfo {
+    /// libcgos reports this range but has no call to read or command the output level, so
+    /// fans can only be influenced through their limits.
+    pub fn output_range(&self) -> RangeInclusive<i32> {
+        self.out_minimum..=self.out_maximum
+    }
+
+    pub fn percent_of_range(&self, current: Rpm) -> f32 {
+        percent_of_range(current.as_f32(), self.minimum as f32, self.maximum as f32)
+    }
+}
+
 impl From<CGOSFANINFO> for FanInfo {
     fn from(info: CGOSFANINFO) -> Self {
         Self {
@@ -117,6 +163,81 @@ impl Into<CGOSFANINFO> for FanInfo {
 }
 
 #[derive(Copy, Clone, Debug)]
+pub enum FanSelector {
+    Index(usize),
+    Type(FanType),
+}
+
+impl FanSelector {
+    pub(crate) fn matches(&self, index: usize, type_: FanType) -> bool {
+        match self {
+            FanSelector::Index(selected) => *selected == index,
+            FanSelector::Type(selected) => *selected == type_,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct FanLimits {
+    pub alarm_high: i32,
+    pub hysteresis_high: i32,
+    pub alarm_low: i32,
+    pub hysteresis_low: i32,
+}
+
+impl FanLimits {
+    pub(crate) fn fits(&self, info: &FanInfo) -> bool {
+        [
+            self.alarm_high,
+            self.hysteresis_high,
+            self.alarm_low,
+            self.hysteresis_low,
+        ]
+        .iter()
+        .all(|limit| (info.minimum..=info.maximum).contains(limit))
+    }
+
+    pub(crate) fn apply_to(&self, info: FanInfo) -> FanInfo {
+        FanInfo {
+            alarm_high: self.alarm_high,
+            hysteresis_high: self.hysteresis_high,
+            alarm_low: self.alarm_low,
+            hysteresis_low: self.hysteresis_low,
+            ..info
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FanConfig {
+    pub fans: Vec<(FanSelector, FanLimits)>,
+}
+
+#[derive(Debug, Default)]
+pub struct FanConfigReport {
+    pub selectors: Vec<SelectorReport>,
+}
+
+/// Outcome of one configured selector, with an entry for every fan it matched. A selector that
+/// matched no fan has no entries.
+#[derive(Debug)]
+pub struct SelectorReport {
+    pub selector: FanSelector,
+    pub fans: Vec<(usize, FanOutcome)>,
+}
+
+#[derive(Debug)]
+pub enum FanOutcome {
+    Configured,
+    /// The limits are outside the measurement range of the fan and were not applied.
+    OutOfRange,
+    /// Reading the fan info or applying the limits failed. For type selectors this is also
+    /// recorded when the info of a fan could not be read to check its type.
+    Failed(CgosError),
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FanType {
     Cpu,
     Box,
@@ -128,6 +249,25 @@ pub enum FanType {
     TopRAMEnvironment,
     BottomRAMEnvironment,
     Other,
+    Unknown(u32),
+}
+
+impl FanType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FanType::Cpu => "cpu",
+            FanType::Box => "box",
+            FanType::Environment => "environment",
+            FanType::Board => "board",
+            FanType::Backplane => "backplane",
+            FanType::Chipsets => "chipsets",
+            FanType::Video => "video",
+            FanType::Other => "other",
+            FanType::TopRAMEnvironment => "top_ram_environment",
+            FanType::BottomRAMEnvironment => "bottom_ram_environment",
+            FanType::Unknown(_) => "unknown",
+        }
+    }
 }
 
 impl Into<u32> for FanType {
@@ -143,6 +283,7 @@ impl Into<u32> for FanType {
             FanType::Other => CGOS_TEMP_OTHER,
             FanType::TopRAMEnvironment => CGOS_TEMP_TOPDIMM_ENV,
             FanType::BottomRAMEnvironment => CGOS_TEMP_BOTDIMM_ENV,
+            FanType::Unknown(value) => value,
         }
     }
 }
@@ -160,7 +301,45 @@ impl From<u32> for FanType {
             CGOS_TEMP_OTHER => FanType::Other,
             CGOS_TEMP_TOPDIMM_ENV => FanType::TopRAMEnvironment,
             CGOS_TEMP_BOTDIMM_ENV => FanType::BottomRAMEnvironment,
-            _ => panic!("unexpected type {value}"),
+            _ => FanType::Unknown(value),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::mem::zeroed;
+
+    use super::Fan;
+    use crate::bindings::{mock, CGOSFANINFO};
+
+    const ERROR: u32 = 0x10;
+
+    #[test]
+    fn info_fails_on_zero_return() {
+        let board = mock::open_board();
+        let fan = Fan::new(&board, 0, 1).unwrap();
+        mock::fail("CgosFanGetInfo", ERROR);
+
+        mock::assert_failed(fan.info(), "CgosFanGetInfo", ERROR);
+    }
+
+    #[test]
+    fn current_fails_on_zero_return() {
+        let board = mock::open_board();
+        let fan = Fan::new(&board, 0, 1).unwrap();
+        mock::fail("CgosFanGetCurrent", ERROR);
+
+        mock::assert_failed(fan.current(), "CgosFanGetCurrent", ERROR);
+    }
+
+    #[test]
+    fn set_limits_fails_on_zero_return() {
+        let board = mock::open_board();
+        let fan = Fan::new(&board, 0, 1).unwrap();
+        let info = unsafe { zeroed::<CGOSFANINFO>() }.into();
+        mock::fail("CgosFanSetLimits", ERROR);
+
+        mock::assert_failed(fan.set_limits(info), "CgosFanSetLimits", ERROR);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    bindings::{
+        CgosIOCount, CgosIOGetDirection, CgosIOGetDirectionCaps, CgosIOIsAvailable, CgosIORead,
+        CgosIOSetDirection, CgosIOWrite,
+    },
+    board::Board,
+    error::{check_amount, CgosError, CgosResult},
+};
+
+pub struct Io<'library> {
+    board: &'library Board<'library>,
+    index: u32,
+}
+
+impl<'library> Io<'library> {
+    pub(crate) fn amount(handle: u32) -> CgosResult<usize> {
+        check_amount(|| unsafe { CgosIOCount(handle) }, "CgosIOCount")
+    }
+
+    pub(crate) fn new(
+        board: &'library Board<'library>,
+        index: usize,
+        amount: usize,
+    ) -> CgosResult<Io<'library>> {
+        if index >= amount {
+            return Err(CgosError::OutOfRange {
+                what: "I/O unit index",
+                value: index,
+                limit: amount,
+            });
+        }
+        Ok(Self {
+            board,
+            index: index.try_into().unwrap(),
+        })
+    }
+
+    pub fn is_available(&self) -> bool {
+        unsafe { CgosIOIsAvailable(self.board.raw_handle(), self.index) != 0 }
+    }
+
+    pub fn read_mask(&self) -> CgosResult<u32> {
+        let mut data = 0;
+        self.board.check_call(
+            unsafe { CgosIORead(self.board.raw_handle(), self.index, &mut data) },
+            "CgosIORead",
+        )?;
+        Ok(data)
+    }
+
+    pub fn write_mask(&self, mask: u32, value: u32) -> CgosResult<()> {
+        let data = (self.read_mask()? & !mask) | (value & mask);
+        self.board.check_call(
+            unsafe { CgosIOWrite(self.board.raw_handle(), self.index, data) },
+            "CgosIOWrite",
+        )
+    }
+
+    pub fn direction(&self) -> CgosResult<u32> {
+        let mut data = 0;
+        self.board.check_call(
+            unsafe { CgosIOGetDirection(self.board.raw_handle(), self.index, &mut data) },
+            "CgosIOGetDirection",
+        )?;
+        Ok(data)
+    }
+
+    pub fn set_direction(&self, inputs: u32) -> CgosResult<()> {
+        self.board.check_call(
+            unsafe { CgosIOSetDirection(self.board.raw_handle(), self.index, inputs) },
+            "CgosIOSetDirection",
+        )
+    }
+
+    pub fn directions(&self) -> CgosResult<IoDirections> {
+        let mut inputs = 0;
+        let mut outputs = 0;
+        self.board.check_call(
+            unsafe {
+                CgosIOGetDirectionCaps(
+                    self.board.raw_handle(),
+                    self.index,
+                    &mut inputs,
+                    &mut outputs,
+                )
+            },
+            "CgosIOGetDirectionCaps",
+        )?;
+        Ok(IoDirections { inputs, outputs })
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct IoDirections {
+    pub inputs: u32,
+    pub outputs: u32,
+}
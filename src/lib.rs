@@ -1,7 +1,25 @@
-mod bindings;
+#[cfg(feature = "tokio")]
+pub mod asynchronous;
+pub mod bindings;
 pub mod board;
+pub mod cmos;
 pub mod congatec;
+pub mod edid;
+pub mod error;
 pub mod fan;
+pub mod io;
+pub mod monitor;
+#[cfg(feature = "embedded-storage")]
+pub mod nor_flash;
+pub mod retry;
 pub mod status;
 pub mod storage_area;
+#[cfg(feature = "thread-safe")]
+pub mod sync;
 pub mod temperature;
+pub mod units;
+pub mod vga;
+pub mod voltage;
+pub mod watchdog;
+
+pub use congatec::is_library_available;
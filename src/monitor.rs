@@ -0,0 +1,94 @@
+use std::{
+    sync::{
+        mpsc::{channel, RecvTimeoutError, Sender},
+        Arc, Mutex, PoisonError,
+    },
+    thread::{spawn, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    board::Board,
+    error::CgosResult,
+    fan::Fan,
+    status::Status,
+    temperature::Temperature,
+    units::{Celsius, Rpm},
+};
+
+/// A sensor of the monitored board, selected by its index.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MonitoredSensor {
+    Temperature(usize),
+    Fan(usize),
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Reading {
+    Temperature(Celsius),
+    Fan(Rpm),
+}
+
+/// Polls the sensors of a shared board on a background thread until dropped. The board is locked
+/// for each round of readings and can be used from other threads in between. Since the thread
+/// outlives any borrow, the board has to come from a leaked `Congatec`; the board itself is shared
+/// and does not need to be leaked.
+pub struct Monitor {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Monitor {
+    /// The callback receives one `(reading, status)` result per sensor, in the order given; a
+    /// failed read only affects the entry of that sensor.
+    pub fn start(
+        board: Arc<Mutex<Board<'static>>>,
+        sensors: Vec<MonitoredSensor>,
+        interval: Duration,
+        mut callback: impl FnMut(&[CgosResult<(Reading, Status)>]) + Send + 'static,
+    ) -> Self {
+        let (stop, stopped) = channel();
+        let thread = spawn(move || loop {
+            let readings: Vec<_> = {
+                let board = board.lock().unwrap_or_else(PoisonError::into_inner);
+                sensors.iter().map(|sensor| read(&board, *sensor)).collect()
+            };
+            callback(&readings);
+            if !matches!(
+                stopped.recv_timeout(interval),
+                Err(RecvTimeoutError::Timeout)
+            ) {
+                break;
+            }
+        });
+        Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+}
+
+fn read(board: &Board, sensor: MonitoredSensor) -> CgosResult<(Reading, Status)> {
+    match sensor {
+        MonitoredSensor::Temperature(index) => {
+            let amount = board.get_number_of_temperatures()?;
+            let (value, status) = Temperature::new(board, index, amount)?.current()?;
+            Ok((Reading::Temperature(value), status))
+        }
+        MonitoredSensor::Fan(index) => {
+            let amount = board.get_number_of_fans()?;
+            let (value, status) = Fan::new(board, index, amount)?.current()?;
+            Ok((Reading::Fan(value), status))
+        }
+    }
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        // disconnecting the channel wakes the thread immediately instead of after the interval
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
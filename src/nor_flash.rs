@@ -0,0 +1,77 @@
+use embedded_storage::nor_flash::{
+    ErrorType, NorFlash, NorFlashError, NorFlashErrorKind, ReadNorFlash,
+};
+
+use crate::{
+    error::{CgosError, CgosResult},
+    storage_area::{StorageArea, StorageAreaType, ERASE_TIMEOUT},
+};
+
+/// The trait constants are fixed at compile time, so the block size of the flash area is a type
+/// parameter and checked against the hardware on construction.
+pub struct FlashStorage<'library, const BLOCK_SIZE: usize> {
+    area: StorageArea<'library>,
+}
+
+impl<'library, const BLOCK_SIZE: usize> FlashStorage<'library, BLOCK_SIZE> {
+    pub fn new(area: StorageArea<'library>) -> Option<Self> {
+        (area.type_() == StorageAreaType::FLASH && area.block_size() == BLOCK_SIZE)
+            .then_some(Self { area })
+    }
+
+    pub fn into_inner(self) -> StorageArea<'library> {
+        self.area
+    }
+}
+
+impl NorFlashError for CgosError {
+    fn kind(&self) -> NorFlashErrorKind {
+        match self {
+            CgosError::OutOfRange { .. } => NorFlashErrorKind::OutOfBounds,
+            CgosError::Unaligned { .. } => NorFlashErrorKind::NotAligned,
+            _ => NorFlashErrorKind::Other,
+        }
+    }
+}
+
+impl<'library, const BLOCK_SIZE: usize> ErrorType for FlashStorage<'library, BLOCK_SIZE> {
+    type Error = CgosError;
+}
+
+impl<'library, const BLOCK_SIZE: usize> ReadNorFlash for FlashStorage<'library, BLOCK_SIZE> {
+    const READ_SIZE: usize = 1;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> CgosResult<()> {
+        self.area.read(offset as usize, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        self.area.size()
+    }
+}
+
+impl<'library, const BLOCK_SIZE: usize> NorFlash for FlashStorage<'library, BLOCK_SIZE> {
+    const WRITE_SIZE: usize = BLOCK_SIZE;
+    const ERASE_SIZE: usize = BLOCK_SIZE;
+
+    fn erase(&mut self, from: u32, to: u32) -> CgosResult<()> {
+        let (from, to) = (from as usize, to as usize);
+        if from > to {
+            return Err(CgosError::OutOfRange {
+                what: "erase start",
+                value: from,
+                limit: to,
+            });
+        }
+        self.area.check_aligned(from, BLOCK_SIZE)?;
+        self.area.check_aligned(to, BLOCK_SIZE)?;
+        self.area.erase_blocking(from, to - from, ERASE_TIMEOUT)
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> CgosResult<()> {
+        let offset = offset as usize;
+        self.area.check_aligned(offset, BLOCK_SIZE)?;
+        self.area.check_aligned(offset + bytes.len(), BLOCK_SIZE)?;
+        self.area.write(offset, bytes)
+    }
+}
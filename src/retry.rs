@@ -0,0 +1,24 @@
+use std::{thread::sleep, time::Duration};
+
+use crate::error::CgosResult;
+
+#[derive(Copy, Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_attempts: usize,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn run<T>(&self, mut operation: impl FnMut() -> CgosResult<T>) -> CgosResult<T> {
+        let mut attempt = 1;
+        loop {
+            match operation() {
+                Err(_) if attempt < self.max_attempts => {
+                    sleep(self.backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt::{self, Display, Formatter};
+
 use bitflags::bitflags;
 
 use crate::bindings::{
@@ -5,6 +7,7 @@ use crate::bindings::{
 };
 
 bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Status: u32 {
         const ACTIVE = CGOS_SENSOR_ACTIVE;
         const ALARM = CGOS_SENSOR_ALARM;
@@ -12,3 +15,31 @@ bitflags! {
         const SHORT_CIRCUIT = CGOS_SENSOR_SHORTCIRCUIT;
     }
 }
+
+impl Status {
+    pub fn active_flags(&self) -> Vec<&'static str> {
+        [
+            (Status::ACTIVE, "ACTIVE"),
+            (Status::ALARM, "ALARM"),
+            (Status::BROKEN, "BROKEN"),
+            (Status::SHORT_CIRCUIT, "SHORT_CIRCUIT"),
+        ]
+        .into_iter()
+        .filter(|(flag, _)| self.contains(*flag))
+        .map(|(_, name)| name)
+        .collect()
+    }
+
+    pub fn is_alarming(&self) -> bool {
+        self.intersects(Status::ALARM | Status::BROKEN | Status::SHORT_CIRCUIT)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(formatter, "NONE");
+        }
+        write!(formatter, "{}", self.active_flags().join(" | "))
+    }
+}
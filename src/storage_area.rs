@@ -1,151 +1,482 @@
-use std::marker::PhantomData;
+use std::{
+    io::{self, Read, Seek, SeekFrom, Write},
+    ops::{Deref, Range},
+    thread::sleep,
+    time::{Duration, Instant},
+};
 
 use bitflags::bitflags;
 
-use crate::bindings::{
-    CgosStorageAreaBlockSize, CgosStorageAreaCount, CgosStorageAreaErase,
-    CgosStorageAreaEraseStatus, CgosStorageAreaIsLocked, CgosStorageAreaLock, CgosStorageAreaRead,
-    CgosStorageAreaSize, CgosStorageAreaType, CgosStorageAreaUnlock, CgosStorageAreaWrite,
-    CGOS_STORAGE_AREA_CMOS, CGOS_STORAGE_AREA_EEPROM, CGOS_STORAGE_AREA_FLASH,
-    CGOS_STORAGE_AREA_RAM, CGOS_STORAGE_AREA_UNKNOWN,
+use crate::{
+    bindings::{
+        CgosStorageAreaBlockSize, CgosStorageAreaCount, CgosStorageAreaErase,
+        CgosStorageAreaEraseStatus, CgosStorageAreaIsLocked, CgosStorageAreaLock,
+        CgosStorageAreaRead, CgosStorageAreaSize, CgosStorageAreaType, CgosStorageAreaUnlock,
+        CgosStorageAreaWrite, CGOS_STORAGE_AREA_CMOS, CGOS_STORAGE_AREA_EEPROM,
+        CGOS_STORAGE_AREA_FLASH, CGOS_STORAGE_AREA_RAM, CGOS_STORAGE_AREA_UNKNOWN,
+    },
+    board::Board,
+    error::{check_amount, CgosError, CgosResult},
 };
 
+/// How long erases issued internally, e.g. by `write_block_aligned`, may take to complete.
+pub(crate) const ERASE_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct StorageArea<'library> {
-    handle: u32,
+    board: &'library Board<'library>,
     unit: u32,
-    _library_lifetime: PhantomData<&'library ()>,
 }
 
 impl<'library> StorageArea<'library> {
-    pub(crate) fn amount(handle: u32, type_: StorageAreaType) -> usize {
-        unsafe { CgosStorageAreaCount(handle, type_.bits()) as usize }
+    pub(crate) fn amount(handle: u32, type_: StorageAreaType) -> CgosResult<usize> {
+        check_amount(
+            || unsafe { CgosStorageAreaCount(handle, type_.bits()) },
+            "CgosStorageAreaCount",
+        )
     }
 
-    pub(crate) fn from_index(handle: u32, index: usize) -> StorageArea<'library> {
+    pub(crate) fn from_index(
+        board: &'library Board<'library>,
+        index: usize,
+    ) -> StorageArea<'library> {
         Self {
-            handle,
+            board,
             unit: index.try_into().unwrap(),
-            _library_lifetime: PhantomData,
         }
     }
 
-    pub(crate) fn from_type(handle: u32, type_: StorageAreaType) -> StorageArea<'library> {
+    pub(crate) fn from_type(
+        board: &'library Board<'library>,
+        type_: StorageAreaType,
+    ) -> StorageArea<'library> {
         Self {
-            handle,
+            board,
             unit: type_.bits(),
-            _library_lifetime: PhantomData,
         }
     }
 
     pub fn type_(&self) -> StorageAreaType {
-        StorageAreaType::from_bits_truncate(unsafe { CgosStorageAreaType(self.handle, self.unit) })
+        StorageAreaType::from_bits_truncate(unsafe {
+            CgosStorageAreaType(self.board.raw_handle(), self.unit)
+        })
     }
 
     pub fn size(&self) -> usize {
-        unsafe { CgosStorageAreaSize(self.handle, self.unit) as usize }
+        unsafe { CgosStorageAreaSize(self.board.raw_handle(), self.unit) as usize }
     }
 
     pub fn block_size(&self) -> usize {
-        unsafe { CgosStorageAreaBlockSize(self.handle, self.unit) as usize }
+        unsafe { CgosStorageAreaBlockSize(self.board.raw_handle(), self.unit) as usize }
+    }
+
+    pub fn describe(&self) -> (StorageAreaType, usize, usize) {
+        (self.type_(), self.size(), self.block_size())
+    }
+
+    pub fn access_granularity(&self) -> AccessGranularity {
+        let block_size = self.block_size().max(1);
+        match self.type_() {
+            StorageAreaType::FLASH => AccessGranularity {
+                read_unit: 1,
+                write_unit: block_size,
+                erase_unit: block_size,
+            },
+            StorageAreaType::EEPROM | StorageAreaType::CMOS | StorageAreaType::RAM => {
+                AccessGranularity {
+                    read_unit: 1,
+                    write_unit: 1,
+                    erase_unit: block_size,
+                }
+            }
+            _ => AccessGranularity {
+                read_unit: block_size,
+                write_unit: block_size,
+                erase_unit: block_size,
+            },
+        }
     }
 
-    pub fn read(&self, offset: usize, data: &mut [u8]) {
-        assert_ne!(
+    pub fn read(&self, offset: usize, data: &mut [u8]) -> CgosResult<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.check_range(offset, data.len())?;
+        self.board.check_call(
             unsafe {
                 CgosStorageAreaRead(
-                    self.handle,
+                    self.board.raw_handle(),
                     self.unit,
                     offset.try_into().unwrap(),
                     data.as_mut_ptr(),
                     data.len().try_into().unwrap(),
                 )
             },
-            0,
-        );
+            "CgosStorageAreaRead",
+        )
     }
 
-    pub fn write(&self, offset: usize, data: &[u8]) {
-        assert_ne!(
+    pub fn write(&self, offset: usize, data: &[u8]) -> CgosResult<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.check_range(offset, data.len())?;
+        self.check_unlocked()?;
+        self.board.check_call(
             unsafe {
                 CgosStorageAreaWrite(
-                    self.handle,
+                    self.board.raw_handle(),
                     self.unit,
                     offset.try_into().unwrap(),
                     data.as_ptr() as *mut _,
                     data.len().try_into().unwrap(),
                 )
             },
-            0,
-        );
+            "CgosStorageAreaWrite",
+        )
+    }
+
+    pub fn write_atomic(&self, offset: usize, data: &[u8]) -> CgosResult<()> {
+        let mut previous = vec![0; data.len()];
+        self.read(offset, &mut previous)?;
+        let result = self.write(offset, data).and_then(|_| {
+            let mut written = vec![0; data.len()];
+            self.read(offset, &mut written)?;
+            if written != data {
+                return Err(CgosError::VerificationFailed {
+                    offset,
+                    length: data.len(),
+                });
+            }
+            Ok(())
+        });
+        match result {
+            Err(error) => match self.write(offset, &previous) {
+                Ok(()) => Err(error),
+                Err(rollback) => Err(CgosError::RollbackFailed {
+                    error: Box::new(error),
+                    rollback: Box::new(rollback),
+                }),
+            },
+            Ok(()) => Ok(()),
+        }
     }
 
-    pub fn erase(&self, offset: usize, length: usize) {
-        assert_ne!(
+    pub fn snapshot(&self) -> CgosResult<StorageSnapshot> {
+        let mut data = vec![0; self.size()];
+        self.read(0, &mut data)?;
+        Ok(StorageSnapshot { data })
+    }
+
+    pub fn erase(&self, offset: usize, length: usize) -> CgosResult<()> {
+        self.check_range(offset, length)?;
+        self.check_unlocked()?;
+        self.board.check_call(
             unsafe {
                 CgosStorageAreaErase(
-                    self.handle,
+                    self.board.raw_handle(),
                     self.unit,
                     offset.try_into().unwrap(),
                     length.try_into().unwrap(),
                 )
             },
-            0,
-        );
+            "CgosStorageAreaErase",
+        )
+    }
+
+    pub fn erase_blocking(
+        &self,
+        offset: usize,
+        length: usize,
+        timeout: Duration,
+    ) -> CgosResult<()> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(10);
+        self.erase(offset, length)?;
+        let start = Instant::now();
+        loop {
+            match self.erase_status(offset, length)? {
+                EraseStatus::Successful => return Ok(()),
+                // an unknown status cannot confirm that the area was erased
+                EraseStatus::Failed | EraseStatus::Unknown(_) => {
+                    return Err(CgosError::EraseFailed { offset, length })
+                }
+                EraseStatus::InProgress if start.elapsed() >= timeout => {
+                    return Err(CgosError::Timeout(timeout))
+                }
+                EraseStatus::InProgress => sleep(POLL_INTERVAL),
+            }
+        }
+    }
+
+    pub fn erase_blocks(&self, range: Range<usize>) -> CgosResult<()> {
+        let block_size = self.block_size().max(1);
+        self.check_aligned(range.start, block_size)?;
+        self.check_aligned(range.end, block_size)?;
+        self.erase(range.start, range.len())
     }
 
-    pub fn erase_status(&self, offset: usize, length: usize) -> EraseStatus {
+    pub fn write_block_aligned(&self, offset: usize, data: &[u8]) -> CgosResult<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.check_range(offset, data.len())?;
+        self.check_unlocked()?;
+        let block_size = self.block_size().max(1);
+        let start = offset / block_size * block_size;
+        let end = ((offset + data.len()).div_ceil(block_size) * block_size).min(self.size());
+        let mut blocks = vec![0; end - start];
+        self.read(start, &mut blocks)?;
+        blocks[offset - start..][..data.len()].copy_from_slice(data);
+        // the range is block aligned by construction; the erase must finish before rewriting
+        self.erase_blocking(start, end - start, ERASE_TIMEOUT)?;
+        self.write(start, &blocks)
+    }
+
+    pub fn erase_status(&self, offset: usize, length: usize) -> CgosResult<EraseStatus> {
+        self.check_range(offset, length)?;
         let mut status = 0;
-        assert_ne!(
+        self.board.check_call(
             unsafe {
                 CgosStorageAreaEraseStatus(
-                    self.handle,
+                    self.board.raw_handle(),
                     self.unit,
                     offset.try_into().unwrap(),
                     length.try_into().unwrap(),
                     &mut status,
                 )
             },
-            0,
-        );
-        status.into()
+            "CgosStorageAreaEraseStatus",
+        )?;
+        Ok(status.into())
     }
 
-    pub fn lock(&self, secret: &[u8]) {
-        assert_ne!(
+    pub fn lock(&self, secret: &[u8]) -> CgosResult<()> {
+        self.board.check_call(
             unsafe {
                 CgosStorageAreaLock(
-                    self.handle,
+                    self.board.raw_handle(),
                     self.unit,
                     0,
                     secret.as_ptr() as *mut _,
                     secret.len().try_into().unwrap(),
                 )
             },
-            0,
-        );
+            "CgosStorageAreaLock",
+        )
     }
 
-    pub fn unlock(&self, secret: &[u8]) {
-        assert_ne!(
+    /// The area is locked again with the same secret when the returned guard is dropped.
+    pub fn unlock<'area>(
+        &'area self,
+        secret: &[u8],
+    ) -> CgosResult<UnlockedStorageArea<'area, 'library>> {
+        self.board.check_call(
             unsafe {
                 CgosStorageAreaUnlock(
-                    self.handle,
+                    self.board.raw_handle(),
                     self.unit,
                     0,
                     secret.as_ptr() as *mut _,
                     secret.len().try_into().unwrap(),
                 )
             },
-            0,
-        );
+            "CgosStorageAreaUnlock",
+        )?;
+        Ok(UnlockedStorageArea {
+            area: self,
+            secret: Some(secret.to_vec()),
+        })
+    }
+
+    /// Only the persistent EEPROM and flash areas can be locked; RAM and CMOS areas reject it.
+    pub fn supports_locking(&self) -> bool {
+        matches!(
+            self.type_(),
+            StorageAreaType::EEPROM | StorageAreaType::FLASH
+        )
     }
 
     pub fn is_locked(&self) -> bool {
-        unsafe { CgosStorageAreaIsLocked(self.handle, self.unit, 0) != 0 }
+        unsafe { CgosStorageAreaIsLocked(self.board.raw_handle(), self.unit, 0) != 0 }
+    }
+
+    fn check_unlocked(&self) -> CgosResult<()> {
+        if self.is_locked() {
+            return Err(CgosError::Locked);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_aligned(&self, value: usize, alignment: usize) -> CgosResult<()> {
+        // the end of the area counts as a block boundary even if the last block is partial
+        if !value.is_multiple_of(alignment) && value != self.size() {
+            return Err(CgosError::Unaligned { value, alignment });
+        }
+        Ok(())
+    }
+
+    fn check_range(&self, offset: usize, length: usize) -> CgosResult<()> {
+        let size = self.size();
+        match offset.checked_add(length) {
+            Some(end) if end <= size => Ok(()),
+            _ => Err(CgosError::OutOfRange {
+                what: "storage area access end",
+                value: offset.saturating_add(length),
+                limit: size,
+            }),
+        }
+    }
+}
+
+#[must_use = "the storage area is locked again when the guard is dropped"]
+pub struct UnlockedStorageArea<'area, 'library> {
+    area: &'area StorageArea<'library>,
+    // taken by `relock` so that drop does not lock a second time
+    secret: Option<Vec<u8>>,
+}
+
+impl<'area, 'library> UnlockedStorageArea<'area, 'library> {
+    pub fn relock(mut self) -> CgosResult<()> {
+        match self.secret.take() {
+            Some(secret) => self.area.lock(&secret),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'area, 'library> Deref for UnlockedStorageArea<'area, 'library> {
+    type Target = StorageArea<'library>;
+
+    fn deref(&self) -> &Self::Target {
+        self.area
+    }
+}
+
+impl<'area, 'library> Drop for UnlockedStorageArea<'area, 'library> {
+    fn drop(&mut self) {
+        if let Some(secret) = self.secret.take() {
+            let _ = self.area.lock(&secret);
+        }
+    }
+}
+
+pub struct StorageAreaCursor<'library> {
+    area: StorageArea<'library>,
+    size: usize,
+    position: usize,
+}
+
+impl<'library> StorageAreaCursor<'library> {
+    pub fn new(area: StorageArea<'library>) -> Self {
+        Self {
+            size: area.size(),
+            area,
+            position: 0,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn into_inner(self) -> StorageArea<'library> {
+        self.area
+    }
+
+    fn remaining(&self, length: usize) -> usize {
+        length.min(self.size.saturating_sub(self.position))
+    }
+}
+
+impl<'library> Read for StorageAreaCursor<'library> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let length = self.remaining(buffer.len());
+        self.area.read(self.position, &mut buffer[..length])?;
+        self.position += length;
+        Ok(length)
+    }
+}
+
+impl<'library> Write for StorageAreaCursor<'library> {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        let length = self.remaining(buffer.len());
+        self.area.write(self.position, &buffer[..length])?;
+        self.position += length;
+        Ok(length)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'library> Seek for StorageAreaCursor<'library> {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        let position = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(delta) => (self.size as u64).checked_add_signed(delta),
+            SeekFrom::Current(delta) => (self.position as u64).checked_add_signed(delta),
+        }
+        .and_then(|position| usize::try_from(position).ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        self.position = position;
+        Ok(position as u64)
+    }
+}
+
+/// Derived from the area type and block size: flash is written and erased in whole blocks,
+/// EEPROM, CMOS and RAM are byte-addressable for reads and writes, and areas of unknown type are
+/// conservatively treated as block-addressable for everything.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AccessGranularity {
+    pub read_unit: usize,
+    pub write_unit: usize,
+    pub erase_unit: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct StorageSnapshot {
+    data: Vec<u8>,
+}
+
+impl StorageSnapshot {
+    pub fn get_u8(&self, offset: usize) -> Option<u8> {
+        self.data.get(offset).copied()
+    }
+
+    pub fn get_u16(&self, offset: usize) -> Option<u16> {
+        self.get_array(offset).map(u16::from_le_bytes)
+    }
+
+    pub fn get_u32(&self, offset: usize) -> Option<u32> {
+        self.get_array(offset).map(u32::from_le_bytes)
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.data
+    }
+
+    fn get_array<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+        self.data
+            .get(offset..offset.checked_add(N)?)
+            .map(|bytes| bytes.try_into().unwrap())
+    }
+}
+
+impl Deref for StorageSnapshot {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
     }
 }
 
 bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct StorageAreaType: u32 {
         const UNKNOWN = CGOS_STORAGE_AREA_UNKNOWN;
         const EEPROM = CGOS_STORAGE_AREA_EEPROM;
@@ -159,6 +490,7 @@ pub enum EraseStatus {
     Successful,
     InProgress,
     Failed,
+    Unknown(u32),
 }
 
 impl From<u32> for EraseStatus {
@@ -167,7 +499,7 @@ impl From<u32> for EraseStatus {
             0 => EraseStatus::Successful,
             1 => EraseStatus::InProgress,
             2 => EraseStatus::Failed,
-            _ => panic!("unexpected status {value}"),
+            _ => EraseStatus::Unknown(value),
         }
     }
 }
@@ -0,0 +1,32 @@
+use std::sync::{Mutex, PoisonError};
+
+use crate::board::Board;
+
+pub struct SyncBoard<'library> {
+    board: Mutex<Board<'library>>,
+}
+
+impl<'library> SyncBoard<'library> {
+    pub fn new(board: Board<'library>) -> Self {
+        Self {
+            board: Mutex::new(board),
+        }
+    }
+
+    pub fn with<T>(&self, operation: impl FnOnce(&Board<'library>) -> T) -> T {
+        let board = self.board.lock().unwrap_or_else(PoisonError::into_inner);
+        operation(&board)
+    }
+
+    pub fn into_inner(self) -> Board<'library> {
+        self.board
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<'library> From<Board<'library>> for SyncBoard<'library> {
+    fn from(board: Board<'library>) -> Self {
+        Self::new(board)
+    }
+}
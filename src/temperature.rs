@@ -1,7 +1,4 @@
-use std::{
-    marker::PhantomData,
-    mem::{size_of, zeroed},
-};
+use std::mem::{size_of, zeroed};
 
 use crate::{
     bindings::{
@@ -10,72 +7,166 @@ use crate::{
         CGOS_TEMP_BOTDIMM_ENV, CGOS_TEMP_BOX, CGOS_TEMP_CHIPSETS, CGOS_TEMP_CPU, CGOS_TEMP_ENV,
         CGOS_TEMP_OTHER, CGOS_TEMP_TOPDIMM_ENV, CGOS_TEMP_VIDEO,
     },
+    board::Board,
+    error::{check_amount, CgosError, CgosResult},
     status::Status,
+    units::Celsius,
 };
 
 pub struct Temperature<'library> {
-    handle: u32,
+    board: &'library Board<'library>,
     index: u32,
-    _library_lifetime: PhantomData<&'library ()>,
 }
 
 impl<'library> Temperature<'library> {
-    pub(crate) fn amount(handle: u32) -> usize {
-        unsafe { CgosTemperatureCount(handle) as usize }
+    pub(crate) fn amount(handle: u32) -> CgosResult<usize> {
+        check_amount(
+            || unsafe { CgosTemperatureCount(handle) },
+            "CgosTemperatureCount",
+        )
     }
 
-    pub(crate) fn new(handle: u32, index: usize) -> Temperature<'library> {
-        Self {
-            handle,
-            index: index.try_into().unwrap(),
-            _library_lifetime: PhantomData,
+    pub(crate) fn new(
+        board: &'library Board<'library>,
+        index: usize,
+        amount: usize,
+    ) -> CgosResult<Temperature<'library>> {
+        if index >= amount {
+            return Err(CgosError::OutOfRange {
+                what: "temperature index",
+                value: index,
+                limit: amount,
+            });
         }
+        Ok(Self {
+            board,
+            index: index.try_into().unwrap(),
+        })
     }
 
-    pub fn info(&self) -> TemperatureInfo {
+    pub fn info(&self) -> CgosResult<TemperatureInfo> {
         let mut info: CGOSTEMPERATUREINFO = unsafe { zeroed() };
         info.dwSize = size_of::<CGOSTEMPERATUREINFO>() as u32;
-        assert_ne!(
-            unsafe { CgosTemperatureGetInfo(self.handle, self.index, &mut info) },
-            0
-        );
-        info.into()
+        self.board.check_call(
+            unsafe { CgosTemperatureGetInfo(self.board.raw_handle(), self.index, &mut info) },
+            "CgosTemperatureGetInfo",
+        )?;
+        Ok(info.into())
     }
 
-    pub fn current(&self) -> (f32, Status) {
+    pub fn current(&self) -> CgosResult<(Celsius, Status)> {
         let mut value = 0;
         let mut flags = 0;
-        assert_ne!(
-            unsafe { CgosTemperatureGetCurrent(self.handle, self.index, &mut value, &mut flags) },
-            0,
-        );
-        (
-            value as i32 as f32 / 1000.0,
+        self.board.check_call(
+            unsafe {
+                CgosTemperatureGetCurrent(
+                    self.board.raw_handle(),
+                    self.index,
+                    &mut value,
+                    &mut flags,
+                )
+            },
+            "CgosTemperatureGetCurrent",
+        )?;
+        Ok((
+            Celsius::from_millidegrees(value),
             Status::from_bits_truncate(flags),
-        )
+        ))
     }
 
-    pub fn set_limits(&self, info: TemperatureInfo) {
+    pub fn current_if_active(&self) -> CgosResult<Option<(Celsius, Status)>> {
+        let (value, status) = self.current()?;
+        Ok(status.contains(Status::ACTIVE).then_some((value, status)))
+    }
+
+    pub fn set_limits(&self, info: TemperatureInfo) -> CgosResult<()> {
         let mut info = info.into();
-        assert_ne!(
-            unsafe { CgosTemperatureSetLimits(self.handle, self.index, &mut info) },
-            0,
-        );
+        self.board.check_call(
+            unsafe { CgosTemperatureSetLimits(self.board.raw_handle(), self.index, &mut info) },
+            "CgosTemperatureSetLimits",
+        )
+    }
+
+    pub fn update_limits(&self, limits: TemperatureLimits) -> CgosResult<()> {
+        self.set_limits(limits.apply_to(self.info()?))
+    }
+
+    pub fn label(&self) -> CgosResult<String> {
+        Ok(format!("temp_{}_{}", self.info()?.type_.name(), self.index))
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub struct TemperatureInfo {
     pub type_: TemperatureType,
     pub status: Status,
     pub alarm: u32,
-    pub resolution: f32,
-    pub minimum: f32,
-    pub maximum: f32,
-    pub alarm_high: f32,
-    pub hysteresis_high: f32,
-    pub alarm_low: f32,
-    pub hysteresis_low: f32,
+    pub resolution: Celsius,
+    pub minimum: Celsius,
+    pub maximum: Celsius,
+    pub alarm_high: Celsius,
+    pub hysteresis_high: Celsius,
+    pub alarm_low: Celsius,
+    pub hysteresis_low: Celsius,
+}
+
+impl TemperatureInfo {
+    pub fn percent_of_range(&self, current: Celsius) -> f32 {
+        percent_of_range(current.0, self.minimum.0, self.maximum.0)
+    }
+}
+
+pub(crate) fn percent_of_range(current: f32, minimum: f32, maximum: f32) -> f32 {
+    if maximum <= minimum {
+        return 0.0;
+    }
+    ((current - minimum) / (maximum - minimum) * 100.0).clamp(0.0, 100.0)
+}
+
+/// Limits left unset keep the value currently configured on the sensor.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TemperatureLimits {
+    alarm_high: Option<Celsius>,
+    hysteresis_high: Option<Celsius>,
+    alarm_low: Option<Celsius>,
+    hysteresis_low: Option<Celsius>,
+}
+
+impl TemperatureLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn alarm_high(mut self, value: Celsius) -> Self {
+        self.alarm_high = Some(value);
+        self
+    }
+
+    pub fn hysteresis_high(mut self, value: Celsius) -> Self {
+        self.hysteresis_high = Some(value);
+        self
+    }
+
+    pub fn alarm_low(mut self, value: Celsius) -> Self {
+        self.alarm_low = Some(value);
+        self
+    }
+
+    pub fn hysteresis_low(mut self, value: Celsius) -> Self {
+        self.hysteresis_low = Some(value);
+        self
+    }
+
+    fn apply_to(&self, info: TemperatureInfo) -> TemperatureInfo {
+        TemperatureInfo {
+            alarm_high: self.alarm_high.unwrap_or(info.alarm_high),
+            hysteresis_high: self.hysteresis_high.unwrap_or(info.hysteresis_high),
+            alarm_low: self.alarm_low.unwrap_or(info.alarm_low),
+            hysteresis_low: self.hysteresis_low.unwrap_or(info.hysteresis_low),
+            ..info
+        }
+    }
 }
 
 impl From<CGOSTEMPERATUREINFO> for TemperatureInfo {
@@ -84,13 +175,13 @@ impl From<CGOSTEMPERATUREINFO> for TemperatureInfo {
             type_: info.dwType.into(),
             status: Status::from_bits_truncate(info.dwFlags),
             alarm: info.dwAlarm,
-            resolution: info.dwRes as i32 as f32 / 1000.0,
-            minimum: info.dwMin as i32 as f32 / 1000.0,
-            maximum: info.dwMax as i32 as f32 / 1000.0,
-            alarm_high: info.dwAlarmHi as i32 as f32 / 1000.0,
-            hysteresis_high: info.dwHystHi as i32 as f32 / 1000.0,
-            alarm_low: info.dwAlarmLo as i32 as f32 / 1000.0,
-            hysteresis_low: info.dwHystLo as i32 as f32 / 1000.0,
+            resolution: Celsius::from_millidegrees(info.dwRes),
+            minimum: Celsius::from_millidegrees(info.dwMin),
+            maximum: Celsius::from_millidegrees(info.dwMax),
+            alarm_high: Celsius::from_millidegrees(info.dwAlarmHi),
+            hysteresis_high: Celsius::from_millidegrees(info.dwHystHi),
+            alarm_low: Celsius::from_millidegrees(info.dwAlarmLo),
+            hysteresis_low: Celsius::from_millidegrees(info.dwHystLo),
         }
     }
 }
@@ -102,17 +193,18 @@ impl Into<CGOSTEMPERATUREINFO> for TemperatureInfo {
             dwType: self.type_.into(),
             dwFlags: self.status.bits(),
             dwAlarm: self.alarm,
-            dwRes: (self.resolution * 1000.0) as i32 as u32,
-            dwMin: (self.minimum * 1000.0) as i32 as u32,
-            dwMax: (self.maximum * 1000.0) as i32 as u32,
-            dwAlarmHi: (self.alarm_high * 1000.0) as i32 as u32,
-            dwHystHi: (self.hysteresis_high * 1000.0) as i32 as u32,
-            dwAlarmLo: (self.alarm_low * 1000.0) as i32 as u32,
-            dwHystLo: (self.hysteresis_low * 1000.0) as i32 as u32,
+            dwRes: self.resolution.to_millidegrees(),
+            dwMin: self.minimum.to_millidegrees(),
+            dwMax: self.maximum.to_millidegrees(),
+            dwAlarmHi: self.alarm_high.to_millidegrees(),
+            dwHystHi: self.hysteresis_high.to_millidegrees(),
+            dwAlarmLo: self.alarm_low.to_millidegrees(),
+            dwHystLo: self.hysteresis_low.to_millidegrees(),
         }
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, Debug)]
 pub enum TemperatureType {
     Cpu,
@@ -125,6 +217,25 @@ pub enum TemperatureType {
     TopRAMEnvironment,
     BottomRAMEnvironment,
     Other,
+    Unknown(u32),
+}
+
+impl TemperatureType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TemperatureType::Cpu => "cpu",
+            TemperatureType::Box => "box",
+            TemperatureType::Environment => "environment",
+            TemperatureType::Board => "board",
+            TemperatureType::Backplane => "backplane",
+            TemperatureType::Chipsets => "chipsets",
+            TemperatureType::Video => "video",
+            TemperatureType::Other => "other",
+            TemperatureType::TopRAMEnvironment => "top_ram_environment",
+            TemperatureType::BottomRAMEnvironment => "bottom_ram_environment",
+            TemperatureType::Unknown(_) => "unknown",
+        }
+    }
 }
 
 impl Into<u32> for TemperatureType {
@@ -140,6 +251,7 @@ impl Into<u32> for TemperatureType {
             TemperatureType::Other => CGOS_TEMP_OTHER,
             TemperatureType::TopRAMEnvironment => CGOS_TEMP_TOPDIMM_ENV,
             TemperatureType::BottomRAMEnvironment => CGOS_TEMP_BOTDIMM_ENV,
+            TemperatureType::Unknown(value) => value,
         }
     }
 }
@@ -157,7 +269,49 @@ impl From<u32> for TemperatureType {
             CGOS_TEMP_OTHER => TemperatureType::Other,
             CGOS_TEMP_TOPDIMM_ENV => TemperatureType::TopRAMEnvironment,
             CGOS_TEMP_BOTDIMM_ENV => TemperatureType::BottomRAMEnvironment,
-            _ => panic!("unexpected type {value}"),
+            _ => TemperatureType::Unknown(value),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::mem::zeroed;
+
+    use super::Temperature;
+    use crate::bindings::{mock, CGOSTEMPERATUREINFO};
+
+    const ERROR: u32 = 0x10;
+
+    #[test]
+    fn info_fails_on_zero_return() {
+        let board = mock::open_board();
+        let temperature = Temperature::new(&board, 0, 1).unwrap();
+        mock::fail("CgosTemperatureGetInfo", ERROR);
+
+        mock::assert_failed(temperature.info(), "CgosTemperatureGetInfo", ERROR);
+    }
+
+    #[test]
+    fn current_fails_on_zero_return() {
+        let board = mock::open_board();
+        let temperature = Temperature::new(&board, 0, 1).unwrap();
+        mock::fail("CgosTemperatureGetCurrent", ERROR);
+
+        mock::assert_failed(temperature.current(), "CgosTemperatureGetCurrent", ERROR);
+    }
+
+    #[test]
+    fn set_limits_fails_on_zero_return() {
+        let board = mock::open_board();
+        let temperature = Temperature::new(&board, 0, 1).unwrap();
+        let info = unsafe { zeroed::<CGOSTEMPERATUREINFO>() }.into();
+        mock::fail("CgosTemperatureSetLimits", ERROR);
+
+        mock::assert_failed(
+            temperature.set_limits(info),
+            "CgosTemperatureSetLimits",
+            ERROR,
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::fmt::{self, Display, Formatter};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Celsius(pub f32);
+
+impl Celsius {
+    pub(crate) fn from_millidegrees(value: u32) -> Self {
+        Self(value as i32 as f32 / 1000.0)
+    }
+
+    pub(crate) fn to_millidegrees(self) -> u32 {
+        (self.0 * 1000.0) as i32 as u32
+    }
+
+    pub fn as_f32(self) -> f32 {
+        self.0
+    }
+
+    pub fn to_fahrenheit(self) -> f32 {
+        self.0 * 9.0 / 5.0 + 32.0
+    }
+
+    pub fn to_kelvin(self) -> f32 {
+        self.0 + 273.15
+    }
+}
+
+impl From<f32> for Celsius {
+    fn from(value: f32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Celsius> for f32 {
+    fn from(value: Celsius) -> Self {
+        value.0
+    }
+}
+
+impl Display for Celsius {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{} °C", self.0)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Rpm(pub i32);
+
+impl Rpm {
+    pub fn as_f32(self) -> f32 {
+        self.0 as f32
+    }
+}
+
+impl From<i32> for Rpm {
+    fn from(value: i32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Rpm> for i32 {
+    fn from(value: Rpm) -> Self {
+        value.0
+    }
+}
+
+impl Display for Rpm {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{} rpm", self.0)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Volt(pub f32);
+
+impl Volt {
+    pub(crate) fn from_millivolts(value: u32) -> Self {
+        Self(value as i32 as f32 / 1000.0)
+    }
+
+    pub(crate) fn to_millivolts(self) -> u32 {
+        (self.0 * 1000.0) as i32 as u32
+    }
+
+    pub fn as_f32(self) -> f32 {
+        self.0
+    }
+}
+
+impl From<f32> for Volt {
+    fn from(value: f32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Volt> for f32 {
+    fn from(value: Volt) -> Self {
+        value.0
+    }
+}
+
+impl Display for Volt {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{} V", self.0)
+    }
+}
@@ -0,0 +1,188 @@
+use std::mem::{size_of, zeroed};
+
+use crate::{
+    bindings::{
+        CgosVgaCount, CgosVgaGetBacklight, CgosVgaGetBacklightEnable, CgosVgaGetContrast,
+        CgosVgaGetContrastEnable, CgosVgaGetInfo, CgosVgaSetBacklight, CgosVgaSetBacklightEnable,
+        CgosVgaSetContrast, CgosVgaSetContrastEnable, CGOSVGAINFO,
+    },
+    board::Board,
+    error::{check_amount, CgosError, CgosResult},
+};
+
+/// Whether a panel supports backlight or contrast control is derived from the maximum setting in
+/// its info: a maximum of zero means unsupported. Getters then return `None` and setters fail
+/// with [`CgosError::Unsupported`] without calling into libcgos.
+pub struct Vga<'library> {
+    board: &'library Board<'library>,
+    index: u32,
+}
+
+impl<'library> Vga<'library> {
+    pub(crate) fn amount(handle: u32) -> CgosResult<usize> {
+        check_amount(|| unsafe { CgosVgaCount(handle) }, "CgosVgaCount")
+    }
+
+    pub(crate) fn new(
+        board: &'library Board<'library>,
+        index: usize,
+        amount: usize,
+    ) -> CgosResult<Vga<'library>> {
+        if index >= amount {
+            return Err(CgosError::OutOfRange {
+                what: "VGA channel index",
+                value: index,
+                limit: amount,
+            });
+        }
+        Ok(Self {
+            board,
+            index: index.try_into().unwrap(),
+        })
+    }
+
+    pub fn info(&self) -> CgosResult<VgaInfo> {
+        let mut info: CGOSVGAINFO = unsafe { zeroed() };
+        info.dwSize = size_of::<CGOSVGAINFO>() as u32;
+        self.board.check_call(
+            unsafe { CgosVgaGetInfo(self.board.raw_handle(), self.index, &mut info) },
+            "CgosVgaGetInfo",
+        )?;
+        Ok(info.into())
+    }
+
+    pub fn backlight(&self) -> CgosResult<Option<u8>> {
+        if !self.info()?.supports_backlight() {
+            return Ok(None);
+        }
+        let mut setting = 0;
+        self.board.check_call(
+            unsafe { CgosVgaGetBacklight(self.board.raw_handle(), self.index, &mut setting) },
+            "CgosVgaGetBacklight",
+        )?;
+        Ok(Some(setting.min(u8::MAX as u32) as u8))
+    }
+
+    pub fn set_backlight(&self, value: u8) -> CgosResult<()> {
+        let info = self.info()?;
+        if !info.supports_backlight() {
+            return Err(CgosError::Unsupported("backlight control"));
+        }
+        let setting = (value as u32).min(info.maximum_backlight);
+        self.board.check_call(
+            unsafe { CgosVgaSetBacklight(self.board.raw_handle(), self.index, setting) },
+            "CgosVgaSetBacklight",
+        )
+    }
+
+    pub fn contrast(&self) -> CgosResult<Option<u8>> {
+        if !self.info()?.supports_contrast() {
+            return Ok(None);
+        }
+        let mut setting = 0;
+        self.board.check_call(
+            unsafe { CgosVgaGetContrast(self.board.raw_handle(), self.index, &mut setting) },
+            "CgosVgaGetContrast",
+        )?;
+        Ok(Some(setting.min(u8::MAX as u32) as u8))
+    }
+
+    pub fn set_contrast(&self, value: u8) -> CgosResult<()> {
+        let info = self.info()?;
+        if !info.supports_contrast() {
+            return Err(CgosError::Unsupported("contrast control"));
+        }
+        let setting = (value as u32).min(info.maximum_contrast);
+        self.board.check_call(
+            unsafe { CgosVgaSetContrast(self.board.raw_handle(), self.index, setting) },
+            "CgosVgaSetContrast",
+        )
+    }
+
+    pub fn is_backlight_enabled(&self) -> CgosResult<Option<bool>> {
+        if !self.info()?.supports_backlight() {
+            return Ok(None);
+        }
+        let mut setting = 0;
+        self.board.check_call(
+            unsafe { CgosVgaGetBacklightEnable(self.board.raw_handle(), self.index, &mut setting) },
+            "CgosVgaGetBacklightEnable",
+        )?;
+        Ok(Some(setting != 0))
+    }
+
+    pub fn set_backlight_enabled(&self, enabled: bool) -> CgosResult<()> {
+        if !self.info()?.supports_backlight() {
+            return Err(CgosError::Unsupported("backlight control"));
+        }
+        self.board.check_call(
+            unsafe {
+                CgosVgaSetBacklightEnable(self.board.raw_handle(), self.index, enabled as u32)
+            },
+            "CgosVgaSetBacklightEnable",
+        )
+    }
+
+    pub fn is_contrast_enabled(&self) -> CgosResult<Option<bool>> {
+        if !self.info()?.supports_contrast() {
+            return Ok(None);
+        }
+        let mut setting = 0;
+        self.board.check_call(
+            unsafe { CgosVgaGetContrastEnable(self.board.raw_handle(), self.index, &mut setting) },
+            "CgosVgaGetContrastEnable",
+        )?;
+        Ok(Some(setting != 0))
+    }
+
+    pub fn set_contrast_enabled(&self, enabled: bool) -> CgosResult<()> {
+        if !self.info()?.supports_contrast() {
+            return Err(CgosError::Unsupported("contrast control"));
+        }
+        self.board.check_call(
+            unsafe {
+                CgosVgaSetContrastEnable(self.board.raw_handle(), self.index, enabled as u32)
+            },
+            "CgosVgaSetContrastEnable",
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct VgaInfo {
+    pub type_: u32,
+    pub flags: u32,
+    pub native_width: u32,
+    pub native_height: u32,
+    pub requested_width: u32,
+    pub requested_height: u32,
+    pub requested_bits_per_pixel: u32,
+    pub maximum_backlight: u32,
+    pub maximum_contrast: u32,
+}
+
+impl VgaInfo {
+    pub fn supports_backlight(&self) -> bool {
+        self.maximum_backlight > 0
+    }
+
+    pub fn supports_contrast(&self) -> bool {
+        self.maximum_contrast > 0
+    }
+}
+
+impl From<CGOSVGAINFO> for VgaInfo {
+    fn from(info: CGOSVGAINFO) -> Self {
+        Self {
+            type_: info.dwType,
+            flags: info.dwFlags,
+            native_width: info.dwNativeWidth,
+            native_height: info.dwNativeHeight,
+            requested_width: info.dwRequestedWidth,
+            requested_height: info.dwRequestedHeight,
+            requested_bits_per_pixel: info.dwRequestedBpp,
+            maximum_backlight: info.dwMaxBacklight,
+            maximum_contrast: info.dwMaxContrast,
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::mem::{size_of, zeroed};
+
+use crate::{
+    bindings::{
+        CgosVoltageCount, CgosVoltageGetCurrent, CgosVoltageGetInfo, CgosVoltageSetLimits,
+        CGOSVOLTAGEINFO, CGOS_VOLTAGE_12V_S0, CGOS_VOLTAGE_33V_S0, CGOS_VOLTAGE_33V_S5,
+        CGOS_VOLTAGE_5V_S0, CGOS_VOLTAGE_5V_S5, CGOS_VOLTAGE_AC, CGOS_VOLTAGE_BAT_CMOS,
+        CGOS_VOLTAGE_BAT_POWER, CGOS_VOLTAGE_CPU_BUS, CGOS_VOLTAGE_CPU_CACHE,
+        CGOS_VOLTAGE_CPU_CORE, CGOS_VOLTAGE_CPU_CORE2, CGOS_VOLTAGE_CPU_IO, CGOS_VOLTAGE_DC,
+        CGOS_VOLTAGE_DC_STANDBY, CGOS_VOLTAGE_OTHER, CGOS_VOLTAGE_VCOREA, CGOS_VOLTAGE_VCOREB,
+    },
+    board::Board,
+    error::{check_amount, CgosError, CgosResult},
+    status::Status,
+    temperature::percent_of_range,
+    units::Volt,
+};
+
+/// Only voltages are monitored; libcgos does not expose supply current or power readings.
+pub struct Voltage<'library> {
+    board: &'library Board<'library>,
+    index: u32,
+}
+
+impl<'library> Voltage<'library> {
+    pub(crate) fn amount(handle: u32) -> CgosResult<usize> {
+        check_amount(|| unsafe { CgosVoltageCount(handle) }, "CgosVoltageCount")
+    }
+
+    pub(crate) fn new(
+        board: &'library Board<'library>,
+        index: usize,
+        amount: usize,
+    ) -> CgosResult<Voltage<'library>> {
+        if index >= amount {
+            return Err(CgosError::OutOfRange {
+                what: "voltage index",
+                value: index,
+                limit: amount,
+            });
+        }
+        Ok(Self {
+            board,
+            index: index.try_into().unwrap(),
+        })
+    }
+
+    pub fn info(&self) -> CgosResult<VoltageInfo> {
+        let mut info: CGOSVOLTAGEINFO = unsafe { zeroed() };
+        info.dwSize = size_of::<CGOSVOLTAGEINFO>() as u32;
+        self.board.check_call(
+            unsafe { CgosVoltageGetInfo(self.board.raw_handle(), self.index, &mut info) },
+            "CgosVoltageGetInfo",
+        )?;
+        Ok(info.into())
+    }
+
+    pub fn current(&self) -> CgosResult<(Volt, Status)> {
+        let mut value = 0;
+        let mut flags = 0;
+        self.board.check_call(
+            unsafe {
+                CgosVoltageGetCurrent(self.board.raw_handle(), self.index, &mut value, &mut flags)
+            },
+            "CgosVoltageGetCurrent",
+        )?;
+        Ok((
+            Volt::from_millivolts(value),
+            Status::from_bits_truncate(flags),
+        ))
+    }
+
+    pub fn current_if_active(&self) -> CgosResult<Option<(Volt, Status)>> {
+        let (value, status) = self.current()?;
+        Ok(status.contains(Status::ACTIVE).then_some((value, status)))
+    }
+
+    pub fn set_limits(&self, info: VoltageInfo) -> CgosResult<()> {
+        let mut info = info.into();
+        self.board.check_call(
+            unsafe { CgosVoltageSetLimits(self.board.raw_handle(), self.index, &mut info) },
+            "CgosVoltageSetLimits",
+        )
+    }
+
+    pub fn label(&self) -> CgosResult<String> {
+        Ok(format!("volt_{}_{}", self.info()?.type_.name(), self.index))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug)]
+pub struct VoltageInfo {
+    pub type_: VoltageType,
+    pub nominal: Volt,
+    pub status: Status,
+    pub alarm: u32,
+    pub resolution: Volt,
+    pub minimum: Volt,
+    pub maximum: Volt,
+    pub alarm_high: Volt,
+    pub hysteresis_high: Volt,
+    pub alarm_low: Volt,
+    pub hysteresis_low: Volt,
+}
+
+impl VoltageInfo {
+    pub fn percent_of_range(&self, current: Volt) -> f32 {
+        percent_of_range(
+            current.as_f32(),
+            self.minimum.as_f32(),
+            self.maximum.as_f32(),
+        )
+    }
+}
+
+impl From<CGOSVOLTAGEINFO> for VoltageInfo {
+    fn from(info: CGOSVOLTAGEINFO) -> Self {
+        Self {
+            type_: info.dwType.into(),
+            nominal: Volt::from_millivolts(info.dwNom),
+            status: Status::from_bits_truncate(info.dwFlags),
+            alarm: info.dwAlarm,
+            resolution: Volt::from_millivolts(info.dwRes),
+            minimum: Volt::from_millivolts(info.dwMin),
+            maximum: Volt::from_millivolts(info.dwMax),
+            alarm_high: Volt::from_millivolts(info.dwAlarmHi),
+            hysteresis_high: Volt::from_millivolts(info.dwHystHi),
+            alarm_low: Volt::from_millivolts(info.dwAlarmLo),
+            hysteresis_low: Volt::from_millivolts(info.dwHystLo),
+        }
+    }
+}
+
+impl From<VoltageInfo> for CGOSVOLTAGEINFO {
+    fn from(info: VoltageInfo) -> Self {
+        CGOSVOLTAGEINFO {
+            dwSize: size_of::<CGOSVOLTAGEINFO>() as u32,
+            dwType: info.type_.into(),
+            dwNom: info.nominal.to_millivolts(),
+            dwFlags: info.status.bits(),
+            dwAlarm: info.alarm,
+            dwRes: info.resolution.to_millivolts(),
+            dwMin: info.minimum.to_millivolts(),
+            dwMax: info.maximum.to_millivolts(),
+            dwAlarmHi: info.alarm_high.to_millivolts(),
+            dwHystHi: info.hysteresis_high.to_millivolts(),
+            dwAlarmLo: info.alarm_low.to_millivolts(),
+            dwHystLo: info.hysteresis_low.to_millivolts(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VoltageType {
+    CpuCore,
+    CpuCore2,
+    CpuIo,
+    CpuBus,
+    CpuCache,
+    Dc,
+    DcStandby,
+    CmosBattery,
+    PowerBattery,
+    Ac,
+    Other,
+    FiveVoltS0,
+    FiveVoltS5,
+    ThreePointThreeVoltS0,
+    ThreePointThreeVoltS5,
+    CoreA,
+    CoreB,
+    TwelveVoltS0,
+    Unknown(u32),
+}
+
+impl VoltageType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            VoltageType::CpuCore => "cpu_core",
+            VoltageType::CpuCore2 => "cpu_core2",
+            VoltageType::CpuIo => "cpu_io",
+            VoltageType::CpuBus => "cpu_bus",
+            VoltageType::CpuCache => "cpu_cache",
+            VoltageType::Dc => "dc",
+            VoltageType::DcStandby => "dc_standby",
+            VoltageType::CmosBattery => "cmos_battery",
+            VoltageType::PowerBattery => "power_battery",
+            VoltageType::Ac => "ac",
+            VoltageType::Other => "other",
+            VoltageType::FiveVoltS0 => "5v_s0",
+            VoltageType::FiveVoltS5 => "5v_s5",
+            VoltageType::ThreePointThreeVoltS0 => "3v3_s0",
+            VoltageType::ThreePointThreeVoltS5 => "3v3_s5",
+            VoltageType::CoreA => "core_a",
+            VoltageType::CoreB => "core_b",
+            VoltageType::TwelveVoltS0 => "12v_s0",
+            VoltageType::Unknown(_) => "unknown",
+        }
+    }
+}
+
+impl From<VoltageType> for u32 {
+    fn from(type_: VoltageType) -> Self {
+        match type_ {
+            VoltageType::CpuCore => CGOS_VOLTAGE_CPU_CORE,
+            VoltageType::CpuCore2 => CGOS_VOLTAGE_CPU_CORE2,
+            VoltageType::CpuIo => CGOS_VOLTAGE_CPU_IO,
+            VoltageType::CpuBus => CGOS_VOLTAGE_CPU_BUS,
+            VoltageType::CpuCache => CGOS_VOLTAGE_CPU_CACHE,
+            VoltageType::Dc => CGOS_VOLTAGE_DC,
+            VoltageType::DcStandby => CGOS_VOLTAGE_DC_STANDBY,
+            VoltageType::CmosBattery => CGOS_VOLTAGE_BAT_CMOS,
+            VoltageType::PowerBattery => CGOS_VOLTAGE_BAT_POWER,
+            VoltageType::Ac => CGOS_VOLTAGE_AC,
+            VoltageType::Other => CGOS_VOLTAGE_OTHER,
+            VoltageType::FiveVoltS0 => CGOS_VOLTAGE_5V_S0,
+            VoltageType::FiveVoltS5 => CGOS_VOLTAGE_5V_S5,
+            VoltageType::ThreePointThreeVoltS0 => CGOS_VOLTAGE_33V_S0,
+            VoltageType::ThreePointThreeVoltS5 => CGOS_VOLTAGE_33V_S5,
+            VoltageType::CoreA => CGOS_VOLTAGE_VCOREA,
+            VoltageType::CoreB => CGOS_VOLTAGE_VCOREB,
+            VoltageType::TwelveVoltS0 => CGOS_VOLTAGE_12V_S0,
+            VoltageType::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<u32> for VoltageType {
+    fn from(value: u32) -> Self {
+        match value {
+            CGOS_VOLTAGE_CPU_CORE => VoltageType::CpuCore,
+            CGOS_VOLTAGE_CPU_CORE2 => VoltageType::CpuCore2,
+            CGOS_VOLTAGE_CPU_IO => VoltageType::CpuIo,
+            CGOS_VOLTAGE_CPU_BUS => VoltageType::CpuBus,
+            CGOS_VOLTAGE_CPU_CACHE => VoltageType::CpuCache,
+            CGOS_VOLTAGE_DC => VoltageType::Dc,
+            CGOS_VOLTAGE_DC_STANDBY => VoltageType::DcStandby,
+            CGOS_VOLTAGE_BAT_CMOS => VoltageType::CmosBattery,
+            CGOS_VOLTAGE_BAT_POWER => VoltageType::PowerBattery,
+            CGOS_VOLTAGE_AC => VoltageType::Ac,
+            CGOS_VOLTAGE_OTHER => VoltageType::Other,
+            CGOS_VOLTAGE_5V_S0 => VoltageType::FiveVoltS0,
+            CGOS_VOLTAGE_5V_S5 => VoltageType::FiveVoltS5,
+            CGOS_VOLTAGE_33V_S0 => VoltageType::ThreePointThreeVoltS0,
+            CGOS_VOLTAGE_33V_S5 => VoltageType::ThreePointThreeVoltS5,
+            CGOS_VOLTAGE_VCOREA => VoltageType::CoreA,
+            CGOS_VOLTAGE_VCOREB => VoltageType::CoreB,
+            CGOS_VOLTAGE_12V_S0 => VoltageType::TwelveVoltS0,
+            _ => VoltageType::Unknown(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::zeroed;
+
+    use super::Voltage;
+    use crate::bindings::{mock, CGOSVOLTAGEINFO};
+
+    const ERROR: u32 = 0x10;
+
+    #[test]
+    fn info_fails_on_zero_return() {
+        let board = mock::open_board();
+        let voltage = Voltage::new(&board, 0, 1).unwrap();
+        mock::fail("CgosVoltageGetInfo", ERROR);
+
+        mock::assert_failed(voltage.info(), "CgosVoltageGetInfo", ERROR);
+    }
+
+    #[test]
+    fn current_fails_on_zero_return() {
+        let board = mock::open_board();
+        let voltage = Voltage::new(&board, 0, 1).unwrap();
+        mock::fail("CgosVoltageGetCurrent", ERROR);
+
+        mock::assert_failed(voltage.current(), "CgosVoltageGetCurrent", ERROR);
+    }
+
+    #[test]
+    fn set_limits_fails_on_zero_return() {
+        let board = mock::open_board();
+        let voltage = Voltage::new(&board, 0, 1).unwrap();
+        let info = unsafe { zeroed::<CGOSVOLTAGEINFO>() }.into();
+        mock::fail("CgosVoltageSetLimits", ERROR);
+
+        mock::assert_failed(voltage.set_limits(info), "CgosVoltageSetLimits", ERROR);
+    }
+}
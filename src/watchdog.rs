@@ -0,0 +1,321 @@
+use std::{
+    cell::Cell,
+    mem::{forget, size_of, zeroed},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    bindings::{
+        CgosWDogCount, CgosWDogDisable, CgosWDogGetInfo, CgosWDogSetConfigStruct, CgosWDogTrigger,
+        CGOSWDCONFIG, CGOSWDINFO, CGOS_WDOG_EVENT_BTN, CGOS_WDOG_EVENT_INT,
+        CGOS_WDOG_EVENT_MAX_STAGES, CGOS_WDOG_EVENT_RST, CGOS_WDOG_EVENT_SCI,
+        CGOS_WDOG_MODE_REBOOT_PC, CGOS_WDOG_MODE_RESTART_OS, CGOS_WDOG_MODE_STAGED,
+        CGOS_WDOG_OPMODE_DISABLED, CGOS_WDOG_OPMODE_EVENT_REPEAT, CGOS_WDOG_OPMODE_ONETIME_TRIG,
+        CGOS_WDOG_OPMODE_SINGLE_EVENT,
+    },
+    board::Board,
+    error::{check_amount, CgosError, CgosResult},
+};
+
+pub struct Watchdog<'library> {
+    board: &'library Board<'library>,
+    index: u32,
+}
+
+impl<'library> Watchdog<'library> {
+    pub(crate) fn amount(handle: u32) -> CgosResult<usize> {
+        check_amount(|| unsafe { CgosWDogCount(handle) }, "CgosWDogCount")
+    }
+
+    pub(crate) fn new(
+        board: &'library Board<'library>,
+        index: usize,
+        amount: usize,
+    ) -> CgosResult<Watchdog<'library>> {
+        if index >= amount {
+            return Err(CgosError::OutOfRange {
+                what: "watchdog index",
+                value: index,
+                limit: amount,
+            });
+        }
+        Ok(Self {
+            board,
+            index: index.try_into().unwrap(),
+        })
+    }
+
+    pub fn info(&self) -> CgosResult<WatchdogInfo> {
+        let mut info: CGOSWDINFO = unsafe { zeroed() };
+        info.dwSize = size_of::<CGOSWDINFO>() as u32;
+        self.board.check_call(
+            unsafe { CgosWDogGetInfo(self.board.raw_handle(), self.index, &mut info) },
+            "CgosWDogGetInfo",
+        )?;
+        Ok(info.into())
+    }
+
+    pub fn trigger(&self) -> CgosResult<()> {
+        self.board.check_call(
+            unsafe { CgosWDogTrigger(self.board.raw_handle(), self.index) },
+            "CgosWDogTrigger",
+        )
+    }
+
+    pub fn disable(&self) -> CgosResult<()> {
+        self.board.check_call(
+            unsafe { CgosWDogDisable(self.board.raw_handle(), self.index) },
+            "CgosWDogDisable",
+        )
+    }
+
+    pub fn configure(&self, config: &WatchdogConfig) -> CgosResult<()> {
+        let mut config = config.to_raw()?;
+        self.board.check_call(
+            unsafe { CgosWDogSetConfigStruct(self.board.raw_handle(), self.index, &mut config) },
+            "CgosWDogSetConfigStruct",
+        )
+    }
+
+    pub fn arm<'watchdog>(
+        &'watchdog self,
+        config: &WatchdogConfig,
+    ) -> CgosResult<WatchdogGuard<'watchdog, 'library>> {
+        self.configure(config)?;
+        Ok(WatchdogGuard {
+            watchdog: self,
+            config: config.clone(),
+            armed: Instant::now(),
+            last_pet: Cell::new(None),
+        })
+    }
+}
+
+/// Keeps an armed watchdog from firing only while `pet` is called in time; dropping the guard
+/// disables the watchdog so that an abandoned guard cannot cause an unexpected reset.
+pub struct WatchdogGuard<'watchdog, 'library> {
+    watchdog: &'watchdog Watchdog<'library>,
+    config: WatchdogConfig,
+    armed: Instant,
+    last_pet: Cell<Option<Instant>>,
+}
+
+impl<'watchdog, 'library> WatchdogGuard<'watchdog, 'library> {
+    pub fn pet(&self) -> CgosResult<()> {
+        self.watchdog.trigger()?;
+        self.last_pet.set(Some(Instant::now()));
+        Ok(())
+    }
+
+    pub fn config(&self) -> &WatchdogConfig {
+        &self.config
+    }
+
+    pub fn last_pet(&self) -> Option<Instant> {
+        self.last_pet.get()
+    }
+
+    /// libcgos reports no countdown, so this is estimated from the configured timeout and the
+    /// last successful `pet`. Until the first `pet`, the initial delay is included as well.
+    pub fn remaining(&self) -> Duration {
+        let deadline = match self.last_pet.get() {
+            Some(last_pet) => last_pet + self.config.expiry(),
+            None => self.armed + self.config.delay + self.config.expiry(),
+        };
+        deadline.saturating_duration_since(Instant::now())
+    }
+
+    pub fn disarm(self) -> CgosResult<()> {
+        let result = self.watchdog.disable();
+        // the watchdog is disabled already, possibly unsuccessfully; don't retry in drop
+        forget(self);
+        result
+    }
+}
+
+impl<'watchdog, 'library> Drop for WatchdogGuard<'watchdog, 'library> {
+    fn drop(&mut self) {
+        let _ = self.watchdog.disable();
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct WatchdogInfo {
+    pub flags: u32,
+    pub minimum_timeout: Duration,
+    pub maximum_timeout: Duration,
+    pub minimum_delay: Duration,
+    pub maximum_delay: Duration,
+    pub operation_modes: u32,
+    pub maximum_stage_count: usize,
+    pub events: u32,
+    pub type_: u32,
+}
+
+impl From<CGOSWDINFO> for WatchdogInfo {
+    fn from(info: CGOSWDINFO) -> Self {
+        Self {
+            flags: info.dwFlags,
+            minimum_timeout: Duration::from_millis(info.dwMinTimeout as u64),
+            maximum_timeout: Duration::from_millis(info.dwMaxTimeout as u64),
+            minimum_delay: Duration::from_millis(info.dwMinDelay as u64),
+            maximum_delay: Duration::from_millis(info.dwMaxDelay as u64),
+            operation_modes: info.dwOpModes,
+            maximum_stage_count: info.dwMaxStageCount as usize,
+            events: info.dwEvents,
+            type_: info.dwType,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WatchdogConfig {
+    pub timeout: Duration,
+    pub delay: Duration,
+    pub mode: WatchdogMode,
+}
+
+impl WatchdogConfig {
+    /// Time from a trigger until the watchdog fires its first event.
+    fn expiry(&self) -> Duration {
+        match &self.mode {
+            WatchdogMode::Staged { stages, .. } => {
+                stages.first().map_or(self.timeout, |stage| stage.timeout)
+            }
+            WatchdogMode::RebootPc | WatchdogMode::RestartOs => self.timeout,
+        }
+    }
+
+    fn to_raw(&self) -> CgosResult<CGOSWDCONFIG> {
+        let mut config: CGOSWDCONFIG = unsafe { zeroed() };
+        config.dwSize = size_of::<CGOSWDCONFIG>() as u32;
+        config.dwTimeout = milliseconds(self.timeout);
+        config.dwDelay = milliseconds(self.delay);
+        match &self.mode {
+            WatchdogMode::RebootPc => config.dwMode = CGOS_WDOG_MODE_REBOOT_PC,
+            WatchdogMode::RestartOs => config.dwMode = CGOS_WDOG_MODE_RESTART_OS,
+            WatchdogMode::Staged {
+                operation_mode,
+                stages,
+            } => {
+                if stages.len() > CGOS_WDOG_EVENT_MAX_STAGES as usize {
+                    return Err(CgosError::OutOfRange {
+                        what: "watchdog stage count",
+                        value: stages.len(),
+                        limit: CGOS_WDOG_EVENT_MAX_STAGES as usize,
+                    });
+                }
+                config.dwMode = CGOS_WDOG_MODE_STAGED;
+                config.dwOpMode = (*operation_mode).into();
+                config.dwStageCount = stages.len() as u32;
+                for (raw, stage) in config.stStages.iter_mut().zip(stages) {
+                    raw.dwTimeout = milliseconds(stage.timeout);
+                    raw.dwEvent = stage.event.into();
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn milliseconds(duration: Duration) -> u32 {
+    duration.as_millis().try_into().unwrap_or(u32::MAX)
+}
+
+#[derive(Clone, Debug)]
+pub enum WatchdogMode {
+    RebootPc,
+    RestartOs,
+    Staged {
+        operation_mode: WatchdogOperationMode,
+        stages: Vec<WatchdogStage>,
+    },
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum WatchdogOperationMode {
+    Disabled,
+    OneTimeTrigger,
+    SingleEvent,
+    EventRepeat,
+}
+
+impl From<WatchdogOperationMode> for u32 {
+    fn from(mode: WatchdogOperationMode) -> Self {
+        match mode {
+            WatchdogOperationMode::Disabled => CGOS_WDOG_OPMODE_DISABLED,
+            WatchdogOperationMode::OneTimeTrigger => CGOS_WDOG_OPMODE_ONETIME_TRIG,
+            WatchdogOperationMode::SingleEvent => CGOS_WDOG_OPMODE_SINGLE_EVENT,
+            WatchdogOperationMode::EventRepeat => CGOS_WDOG_OPMODE_EVENT_REPEAT,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct WatchdogStage {
+    pub timeout: Duration,
+    pub event: WatchdogEvent,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum WatchdogEvent {
+    Interrupt,
+    SystemControlInterrupt,
+    Reset,
+    PowerButton,
+}
+
+impl From<WatchdogEvent> for u32 {
+    fn from(event: WatchdogEvent) -> Self {
+        match event {
+            WatchdogEvent::Interrupt => CGOS_WDOG_EVENT_INT,
+            WatchdogEvent::SystemControlInterrupt => CGOS_WDOG_EVENT_SCI,
+            WatchdogEvent::Reset => CGOS_WDOG_EVENT_RST,
+            WatchdogEvent::PowerButton => CGOS_WDOG_EVENT_BTN,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Watchdog, WatchdogConfig, WatchdogMode};
+    use crate::bindings::mock;
+
+    const ERROR: u32 = 0x10;
+
+    fn config() -> WatchdogConfig {
+        WatchdogConfig {
+            timeout: Duration::from_secs(60),
+            delay: Duration::from_secs(30),
+            mode: WatchdogMode::RebootPc,
+        }
+    }
+
+    #[test]
+    fn remaining_includes_delay_until_first_pet() {
+        let board = mock::open_board();
+        let watchdog = Watchdog::new(&board, 0, 1).unwrap();
+        let guard = watchdog.arm(&config()).unwrap();
+
+        assert!(guard.last_pet().is_none());
+        assert!(guard.remaining() > Duration::from_secs(60));
+        assert!(guard.remaining() <= Duration::from_secs(90));
+
+        guard.pet().unwrap();
+        assert!(guard.last_pet().is_some());
+        assert!(guard.remaining() <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn failed_pet_is_not_recorded() {
+        let board = mock::open_board();
+        let watchdog = Watchdog::new(&board, 0, 1).unwrap();
+        let guard = watchdog.arm(&config()).unwrap();
+        mock::fail("CgosWDogTrigger", ERROR);
+
+        mock::assert_failed(guard.pet(), "CgosWDogTrigger", ERROR);
+        assert!(guard.last_pet().is_none());
+    }
+}